@@ -0,0 +1,107 @@
+// Copyright © 2025 Stephan Kunz
+//! Benchmarks comparing the lock contention of a single shard [`Databoard`] with sharded ones.
+//!
+//! A single shard behaves like a [`Databoard`] with one board-wide lock.
+//! Run with `cargo bench --bench database`.
+
+#![allow(missing_docs)]
+#![allow(clippy::unwrap_used)]
+
+use databoard::Databoard;
+use std::{
+	hint::black_box,
+	sync::{Arc, Barrier},
+	thread,
+	time::{Duration, Instant},
+};
+
+const THREADS: usize = 8;
+const KEYS: usize = 64;
+const ROUNDS: usize = 200;
+
+/// Each thread creates, reads, updates and deletes its own distinct keys.
+fn create_delete(shards: usize) -> Duration {
+	let board = Databoard::with_shards(None, None, false, shards);
+	let keys: Arc<Vec<Vec<String>>> = Arc::new(
+		(0..THREADS)
+			.map(|t| {
+				(0..KEYS)
+					.map(|k| format!("key_{t}_{k}"))
+					.collect()
+			})
+			.collect(),
+	);
+	let barrier = Arc::new(Barrier::new(THREADS + 1));
+	let handles: Vec<_> = (0..THREADS)
+		.map(|t| {
+			let board = board.clone();
+			let keys = keys.clone();
+			let barrier = barrier.clone();
+			thread::spawn(move || {
+				barrier.wait();
+				for round in 0..ROUNDS {
+					for key in &keys[t] {
+						board.set(key, round).unwrap();
+						black_box(board.get::<usize>(key).unwrap());
+						board.set(key, round + 1).unwrap();
+						black_box(board.delete::<usize>(key).unwrap());
+					}
+				}
+			})
+		})
+		.collect();
+	let start = Instant::now();
+	barrier.wait();
+	for handle in handles {
+		handle.join().unwrap();
+	}
+	start.elapsed()
+}
+
+/// Each thread reads and updates keys shared by all threads.
+fn read_update(shards: usize) -> Duration {
+	let board = Databoard::with_shards(None, None, false, shards);
+	let keys: Arc<Vec<String>> = Arc::new((0..KEYS).map(|k| format!("key_{k}")).collect());
+	for key in keys.iter() {
+		board.set(key, 0_usize).unwrap();
+	}
+	let barrier = Arc::new(Barrier::new(THREADS + 1));
+	let handles: Vec<_> = (0..THREADS)
+		.map(|_| {
+			let board = board.clone();
+			let keys = keys.clone();
+			let barrier = barrier.clone();
+			thread::spawn(move || {
+				barrier.wait();
+				for round in 0..ROUNDS {
+					for key in keys.iter() {
+						black_box(board.get::<usize>(key).unwrap());
+						board.set(key, round).unwrap();
+					}
+				}
+			})
+		})
+		.collect();
+	let start = Instant::now();
+	barrier.wait();
+	for handle in handles {
+		handle.join().unwrap();
+	}
+	start.elapsed()
+}
+
+fn main() {
+	for (name, bench) in [
+		("create/delete", create_delete as fn(usize) -> Duration),
+		("read/update", read_update),
+	] {
+		for shards in [1, 8, 16] {
+			// best of 5 runs
+			let best = (0..5)
+				.map(|_| bench(shards))
+				.min()
+				.unwrap_or_default();
+			println!("{name:<14} {THREADS} threads, {shards:>2} shard(s): {best:?}");
+		}
+	}
+}
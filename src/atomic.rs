@@ -0,0 +1,220 @@
+// Copyright © 2025 Stephan Kunz
+//! Lock-free storage for small [`Copy`] values in a [`Databoard`](crate::databoard::Databoard).
+
+use crate::sequence::{SequenceCounter, SequenceId};
+use alloc::boxed::Box;
+use core::any::{Any, TypeId};
+use portable_atomic::{AtomicU64, Ordering};
+
+// region:		--- AtomicValue
+mod private {
+	/// Prevents implementations of [`AtomicValue`](super::AtomicValue) outside of this crate.
+	pub trait Sealed {}
+}
+
+/// Small [`Copy`] types which can be stored lock-free in a [`Databoard`](crate::databoard::Databoard).
+///
+/// The trait is implemented for `bool`, all integer types up to 64 bit, `f32` and `f64`.
+pub trait AtomicValue: private::Sealed + Any + Copy + core::fmt::Debug + Send + Sync {
+	/// Converts the value into its bit representation.
+	fn to_bits(self) -> u64;
+	/// Converts a bit representation back into a value.
+	fn from_bits(bits: u64) -> Self;
+}
+
+impl private::Sealed for bool {}
+impl AtomicValue for bool {
+	fn to_bits(self) -> u64 {
+		u64::from(self)
+	}
+
+	fn from_bits(bits: u64) -> Self {
+		bits != 0
+	}
+}
+
+impl private::Sealed for f32 {}
+impl AtomicValue for f32 {
+	fn to_bits(self) -> u64 {
+		u64::from(self.to_bits())
+	}
+
+	#[allow(clippy::cast_possible_truncation)]
+	fn from_bits(bits: u64) -> Self {
+		Self::from_bits(bits as u32)
+	}
+}
+
+impl private::Sealed for f64 {}
+impl AtomicValue for f64 {
+	fn to_bits(self) -> u64 {
+		self.to_bits()
+	}
+
+	fn from_bits(bits: u64) -> Self {
+		Self::from_bits(bits)
+	}
+}
+
+/// Implements [`AtomicValue`] for integer types.
+macro_rules! atomic_integer {
+	($($t:ty),*) => {
+		$(
+			impl private::Sealed for $t {}
+			impl AtomicValue for $t {
+				#[allow(clippy::cast_sign_loss, clippy::cast_lossless)]
+				fn to_bits(self) -> u64 {
+					self as u64
+				}
+
+				#[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
+				fn from_bits(bits: u64) -> Self {
+					bits as Self
+				}
+			}
+		)*
+	};
+}
+
+atomic_integer!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
+// endregion:	--- AtomicValue
+
+// region:		--- AtomicEntry
+/// A lock-free entry of a [`Database`](crate::database::Database) holding an [`AtomicValue`].
+///
+/// The value is stored type erased as bits, the type specific conversions are kept as function pointers.
+pub struct AtomicEntry {
+	sequence_id: SequenceCounter,
+	bits: AtomicU64,
+	type_id: TypeId,
+	type_name: &'static str,
+	/// Writes the value into an `Option<T>` given as `&mut dyn Any`.
+	load: fn(u64, &mut dyn Any),
+	/// Converts a `&T` given as `&dyn Any` into bits.
+	store: fn(&dyn Any) -> Option<u64>,
+	/// Converts bits into a boxed value.
+	boxed: fn(u64) -> Box<dyn Any + Send + Sync>,
+	/// Formats the value.
+	debug: fn(u64, &mut core::fmt::Formatter<'_>) -> core::fmt::Result,
+}
+
+impl core::fmt::Debug for AtomicEntry {
+	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+		(self.debug)(self.bits.load(Ordering::Acquire), f)
+	}
+}
+
+impl AtomicEntry {
+	/// Creates a new `AtomicEntry`.
+	pub fn new<T: AtomicValue>(value: T) -> Self {
+		Self {
+			sequence_id: SequenceCounter::new(1),
+			bits: AtomicU64::new(value.to_bits()),
+			type_id: TypeId::of::<T>(),
+			type_name: core::any::type_name::<T>(),
+			load: |bits, out| {
+				if let Some(out) = out.downcast_mut::<Option<T>>() {
+					*out = Some(T::from_bits(bits));
+				}
+			},
+			store: |value| value.downcast_ref::<T>().map(|t| t.to_bits()),
+			boxed: |bits| Box::new(T::from_bits(bits)),
+			debug: |bits, f| write!(f, "{:?}", T::from_bits(bits)),
+		}
+	}
+
+	/// Returns an independent copy of the entry including its sequence id.
+	#[must_use]
+	pub fn duplicate(&self) -> Self {
+		Self {
+			sequence_id: SequenceCounter::new(self.sequence_id()),
+			bits: AtomicU64::new(self.bits.load(Ordering::Acquire)),
+			..*self
+		}
+	}
+
+	/// Returns `true` if the stored value is of type `T`.
+	#[must_use]
+	pub fn is<T: Any>(&self) -> bool {
+		self.type_id == TypeId::of::<T>()
+	}
+
+	/// Returns the stored value, if it is of type `T`.
+	#[must_use]
+	pub fn load<T: Any>(&self) -> Option<T> {
+		let mut value: Option<T> = None;
+		if self.is::<T>() {
+			(self.load)(self.bits.load(Ordering::Acquire), &mut value);
+		}
+		value
+	}
+
+	/// Returns the stored value boxed.
+	#[must_use]
+	pub fn boxed(&self) -> Box<dyn Any + Send + Sync> {
+		(self.boxed)(self.bits.load(Ordering::Acquire))
+	}
+
+	/// Returns the current change iteration value.
+	#[must_use]
+	pub fn sequence_id(&self) -> SequenceId {
+		self.sequence_id.get()
+	}
+
+	/// Returns the name of the stored type.
+	#[must_use]
+	pub const fn type_name(&self) -> &'static str {
+		self.type_name
+	}
+
+	/// Replaces the stored value with `value` and returns the old value, if it is of type `T`.
+	/// The sequence id is increased.
+	///
+	/// The value is published before the sequence id is increased, both with release ordering.
+	/// So a reader, which observes the increased sequence id, also observes the new value,
+	/// while a reader may observe the new value together with the old sequence id.
+	/// To detect changes, read the sequence id first and the value afterwards.
+	pub fn swap<T: Any>(&self, value: T) -> Option<T> {
+		let bits = (self.store)(&value)?;
+		let mut old: Option<T> = None;
+		(self.load)(self.bits.swap(bits, Ordering::AcqRel), &mut old);
+		// must happen after publishing the value, see above
+		self.sequence_id.increment();
+		old
+	}
+}
+// endregion:	--- AtomicEntry
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	// check, that the auto traits are available
+	const fn is_normal<T: Sized + Send + Sync>() {}
+
+	#[test]
+	const fn normal_types() {
+		is_normal::<AtomicEntry>();
+	}
+
+	#[test]
+	fn conversions() {
+		fn roundtrip<T: AtomicValue + PartialEq>(value: T) {
+			assert!(T::from_bits(value.to_bits()) == value);
+		}
+		roundtrip(true);
+		roundtrip(false);
+		roundtrip(-1.5_f32);
+		roundtrip(f64::MAX);
+		roundtrip(i8::MIN);
+		roundtrip(i16::MIN);
+		roundtrip(i32::MIN);
+		roundtrip(i64::MIN);
+		roundtrip(isize::MIN);
+		roundtrip(u8::MAX);
+		roundtrip(u16::MAX);
+		roundtrip(u32::MAX);
+		roundtrip(u64::MAX);
+		roundtrip(usize::MAX);
+	}
+}
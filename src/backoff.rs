@@ -0,0 +1,121 @@
+// Copyright © 2025 Stephan Kunz
+//! Strategies for waiting on locked entries of a [`Databoard`](crate::databoard::Databoard).
+
+#[cfg(feature = "std")]
+extern crate std;
+
+/// A strategy to wait between attempts to lock an entry.
+///
+/// Every closure `FnMut() -> bool` is a [`Backoff`], which allows to plug in
+/// any kind of clock, e.g. a hardware timer on an embedded system.
+pub trait Backoff {
+	/// Waits before the next attempt to lock an entry.
+	/// Returns `false` if no further attempt shall be made.
+	fn backoff(&mut self) -> bool;
+}
+
+impl<F: FnMut() -> bool> Backoff for F {
+	fn backoff(&mut self) -> bool {
+		self()
+	}
+}
+
+/// Maximum exponent for the number of spins between two attempts.
+const MAX_SPIN_EXPONENT: u32 = 6;
+
+/// A [`Backoff`] with a bounded number of retries.
+/// Between two attempts it spins with exponentially increasing duration.
+#[derive(Clone, Debug)]
+pub struct Retries {
+	remaining: usize,
+	exponent: u32,
+}
+
+impl Backoff for Retries {
+	fn backoff(&mut self) -> bool {
+		if self.remaining == 0 {
+			return false;
+		}
+		self.remaining -= 1;
+		for _ in 0..(1_u32 << self.exponent) {
+			core::hint::spin_loop();
+		}
+		if self.exponent < MAX_SPIN_EXPONENT {
+			self.exponent += 1;
+		}
+		true
+	}
+}
+
+impl Retries {
+	/// Creates a [`Retries`] backoff which allows `retries` further attempts.
+	#[must_use]
+	pub const fn new(retries: usize) -> Self {
+		Self {
+			remaining: retries,
+			exponent: 0,
+		}
+	}
+}
+
+/// A [`Backoff`] retrying until a deadline is reached.
+/// Between two attempts it yields the current thread.
+#[cfg(feature = "std")]
+#[derive(Clone, Debug)]
+pub struct Timeout {
+	/// `None` if the deadline is not representable, so attempts are allowed forever.
+	deadline: Option<std::time::Instant>,
+}
+
+#[cfg(feature = "std")]
+impl Backoff for Timeout {
+	fn backoff(&mut self) -> bool {
+		if self
+			.deadline
+			.is_some_and(|deadline| std::time::Instant::now() >= deadline)
+		{
+			return false;
+		}
+		std::thread::yield_now();
+		true
+	}
+}
+
+#[cfg(feature = "std")]
+impl Timeout {
+	/// Creates a [`Timeout`] backoff which allows attempts for the given `duration`.
+	/// A `duration` too large to compute a deadline, e.g. [`Duration::MAX`](core::time::Duration::MAX),
+	/// allows attempts without a time limit.
+	#[must_use]
+	pub fn new(duration: core::time::Duration) -> Self {
+		Self {
+			deadline: std::time::Instant::now().checked_add(duration),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	// check, that the auto traits are available
+	const fn is_normal<T: Sized + Send + Sync>() {}
+
+	#[test]
+	const fn normal_types() {
+		is_normal::<Retries>();
+		#[cfg(feature = "std")]
+		is_normal::<Timeout>();
+	}
+
+	#[test]
+	#[cfg(feature = "std")]
+	fn timeout() {
+		let mut timeout = Timeout::new(core::time::Duration::ZERO);
+		assert!(!timeout.backoff());
+		// no overflow, but no deadline
+		let mut timeout = Timeout::new(core::time::Duration::MAX);
+		assert!(timeout.backoff());
+		assert!(timeout.backoff());
+	}
+}
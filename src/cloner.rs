@@ -0,0 +1,124 @@
+// Copyright © 2025 Stephan Kunz
+//! Implements the [`CloneRegistry`] used to copy whole [`Databoard`](crate::Databoard)s.
+
+use crate::{Error, error::Result};
+use alloc::{boxed::Box, vec::Vec};
+use core::any::{Any, TypeId};
+
+/// Type erased clone of a value.
+type Duplicate = fn(&(dyn Any + Send + Sync)) -> Option<Box<dyn Any + Send + Sync>>;
+
+/// Type erased comparison of two values of the same type.
+type Compare = fn(&(dyn Any + Send + Sync), &(dyn Any + Send + Sync)) -> bool;
+
+/// Returns a boxed clone of `value`, if it is of type `T`.
+fn duplicate<T: Any + Clone + Send + Sync>(value: &(dyn Any + Send + Sync)) -> Option<Box<dyn Any + Send + Sync>> {
+	value
+		.downcast_ref::<T>()
+		.map(|t| Box::new(t.clone()) as Box<dyn Any + Send + Sync>)
+}
+
+/// Registry of the types, which can be copied by
+/// [`Databoard::deep_clone`](crate::Databoard::deep_clone) and [`Databoard::fork`](crate::Databoard::fork).
+///
+/// Lock-free entries holding an [`AtomicValue`](crate::AtomicValue) are always copied.
+///
+/// Types registered with [`CloneRegistry::register_comparable`] are compared by value
+/// in [`Databoard::diff`](crate::databoard::DataboardInner::diff).
+#[derive(Default)]
+pub struct CloneRegistry {
+	types: Vec<(TypeId, Duplicate, Option<Compare>)>,
+}
+
+impl core::fmt::Debug for CloneRegistry {
+	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+		write!(f, "CloneRegistry {{ types: {} }}", self.types.len())
+	}
+}
+
+impl CloneRegistry {
+	/// Creates an empty [`CloneRegistry`].
+	#[must_use]
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Returns a copy of `value` stored under `key`.
+	/// # Errors
+	/// - [`Error::Unregistered`] if the type of `value` is not registered.
+	pub(crate) fn clone_value(&self, key: &str, value: &(dyn Any + Send + Sync)) -> Result<Box<dyn Any + Send + Sync>> {
+		let type_id = value.type_id();
+		self.types
+			.iter()
+			.find(|(id, ..)| *id == type_id)
+			.and_then(|(_, duplicate, _)| duplicate(value))
+			.ok_or_else(|| Error::Unregistered { key: key.into() })
+	}
+
+	/// Returns whether `a` and `b` are equal, if their type is registered as comparable.
+	/// Values of different types are never equal.
+	pub(crate) fn equals(&self, a: &(dyn Any + Send + Sync), b: &(dyn Any + Send + Sync)) -> Option<bool> {
+		let type_id = a.type_id();
+		if type_id != b.type_id() {
+			return Some(false);
+		}
+		self.types
+			.iter()
+			.find(|(id, ..)| *id == type_id)
+			.and_then(|(.., compare)| *compare)
+			.map(|compare| compare(a, b))
+	}
+
+	/// Returns `true` if the type `T` is registered as comparable, otherwise `false`.
+	#[must_use]
+	pub fn is_comparable<T: Any>(&self) -> bool {
+		self.types
+			.iter()
+			.any(|(id, _, compare)| *id == TypeId::of::<T>() && compare.is_some())
+	}
+
+	/// Returns `true` if the type `T` is registered, otherwise `false`.
+	#[must_use]
+	pub fn is_registered<T: Any>(&self) -> bool {
+		self.types
+			.iter()
+			.any(|(id, ..)| *id == TypeId::of::<T>())
+	}
+
+	/// Registers the type `T`, registering a type twice has no effect.
+	pub fn register<T: Any + Clone + Send + Sync>(&mut self) {
+		if !self.is_registered::<T>() {
+			self.types
+				.push((TypeId::of::<T>(), duplicate::<T>, None));
+		}
+	}
+
+	/// Registers the type `T` including its comparison,
+	/// an already registered type `T` becomes comparable.
+	pub fn register_comparable<T: Any + Clone + PartialEq + Send + Sync>(&mut self) {
+		let compare: Compare = |a, b| a.downcast_ref::<T>() == b.downcast_ref::<T>();
+		if let Some(registered) = self
+			.types
+			.iter_mut()
+			.find(|(id, ..)| *id == TypeId::of::<T>())
+		{
+			registered.2 = Some(compare);
+		} else {
+			self.types
+				.push((TypeId::of::<T>(), duplicate::<T>, Some(compare)));
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	// check, that the auto traits are available
+	const fn is_normal<T: Sized + Send + Sync>() {}
+
+	#[test]
+	const fn normal_types() {
+		is_normal::<CloneRegistry>();
+	}
+}
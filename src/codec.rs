@@ -0,0 +1,306 @@
+// Copyright © 2025 Stephan Kunz
+//! Serialization of [`Databoard`] entries for remote access.
+
+use crate::{ConstString, Error, databoard::DataboardInner, error::Result};
+use alloc::{string::String, vec::Vec};
+use core::any::{Any, TypeId};
+
+// region:		--- Serial
+/// Types, which can be transferred to a remote [`Databoard`](crate::Databoard).
+///
+/// The trait is implemented for `bool`, all integer types, `f32`, `f64`, [`String`] and [`Vec`]s of [`Serial`] types.
+/// Own types can be serialized by composing the implementations of their fields.
+pub trait Serial: Sized {
+	/// Appends the serialized value to `out`.
+	fn serialize(&self, out: &mut Vec<u8>);
+
+	/// Reads a value from the front of `bytes`, advancing `bytes` behind the read value.
+	/// Returns `None` if `bytes` do not contain a valid value.
+	fn deserialize(bytes: &mut &[u8]) -> Option<Self>;
+}
+
+/// Takes `N` bytes from the front of `bytes`.
+fn take<const N: usize>(bytes: &mut &[u8]) -> Option<[u8; N]> {
+	let (head, tail) = bytes.split_first_chunk::<N>()?;
+	*bytes = tail;
+	Some(*head)
+}
+
+/// Implements [`Serial`] for number types using their little endian representation.
+macro_rules! serial_number {
+	($($t:ty),*) => {
+		$(
+			impl Serial for $t {
+				fn serialize(&self, out: &mut Vec<u8>) {
+					out.extend_from_slice(&self.to_le_bytes());
+				}
+
+				fn deserialize(bytes: &mut &[u8]) -> Option<Self> {
+					take(bytes).map(Self::from_le_bytes)
+				}
+			}
+		)*
+	};
+}
+
+serial_number!(f32, f64, i8, i16, i32, i64, i128, u8, u16, u32, u64, u128);
+
+impl Serial for bool {
+	fn serialize(&self, out: &mut Vec<u8>) {
+		out.push(u8::from(*self));
+	}
+
+	fn deserialize(bytes: &mut &[u8]) -> Option<Self> {
+		match take::<1>(bytes)? {
+			[0] => Some(false),
+			[1] => Some(true),
+			_ => None,
+		}
+	}
+}
+
+/// `isize` is transferred as `i64`, to be independent of the platforms.
+impl Serial for isize {
+	fn serialize(&self, out: &mut Vec<u8>) {
+		(*self as i64).serialize(out);
+	}
+
+	fn deserialize(bytes: &mut &[u8]) -> Option<Self> {
+		Self::try_from(i64::deserialize(bytes)?).ok()
+	}
+}
+
+/// `usize` is transferred as `u64`, to be independent of the platforms.
+impl Serial for usize {
+	fn serialize(&self, out: &mut Vec<u8>) {
+		(*self as u64).serialize(out);
+	}
+
+	fn deserialize(bytes: &mut &[u8]) -> Option<Self> {
+		Self::try_from(u64::deserialize(bytes)?).ok()
+	}
+}
+
+/// A [`String`] is transferred as its length followed by the UTF-8 bytes.
+impl Serial for String {
+	fn serialize(&self, out: &mut Vec<u8>) {
+		self.len().serialize(out);
+		out.extend_from_slice(self.as_bytes());
+	}
+
+	fn deserialize(bytes: &mut &[u8]) -> Option<Self> {
+		let len = usize::deserialize(bytes)?;
+		let (head, tail) = bytes.split_at_checked(len)?;
+		*bytes = tail;
+		Self::from_utf8(head.to_vec()).ok()
+	}
+}
+
+/// A [`Vec`] is transferred as its length followed by the elements.
+impl<T: Serial> Serial for Vec<T> {
+	fn serialize(&self, out: &mut Vec<u8>) {
+		self.len().serialize(out);
+		for item in self {
+			item.serialize(out);
+		}
+	}
+
+	fn deserialize(bytes: &mut &[u8]) -> Option<Self> {
+		let len = usize::deserialize(bytes)?;
+		// do not trust the length for preallocation
+		let mut vec = Self::with_capacity(len.min(bytes.len()));
+		for _ in 0..len {
+			vec.push(T::deserialize(bytes)?);
+		}
+		Some(vec)
+	}
+}
+// endregion:	--- Serial
+
+// region:		--- Registry
+/// Type erased store of a serialized value, returning the serialized old value.
+type Setter = fn(&DataboardInner, &str, &[u8]) -> Result<Option<Vec<u8>>>;
+
+/// Type erased access to a [`Databoard`](crate::Databoard) for a registered type.
+struct Registered {
+	name: ConstString,
+	type_id: TypeId,
+	/// Returns the serialized value stored under a key.
+	get: fn(&DataboardInner, &str) -> Result<Vec<u8>>,
+	/// Stores a serialized value under a key and returns the serialized old value.
+	set: Setter,
+	/// Deletes the value stored under a key and returns it serialized.
+	delete: fn(&DataboardInner, &str) -> Result<Vec<u8>>,
+}
+
+/// Registry of the types, which can be transferred to a remote [`Databoard`](crate::Databoard).
+///
+/// Each type is registered with a name, which identifies the type on both sides of the transfer,
+/// so all participants must register the same types with the same names.
+#[derive(Default)]
+pub struct Registry {
+	types: Vec<Registered>,
+}
+
+impl core::fmt::Debug for Registry {
+	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+		write!(f, "Registry {{ ")?;
+		let names: Vec<&str> = self
+			.types
+			.iter()
+			.map(|registered| registered.name.as_ref())
+			.collect();
+		write!(f, "{names:?}")?;
+		write!(f, " }}")
+	}
+}
+
+impl Registry {
+	/// Creates an empty [`Registry`].
+	#[must_use]
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Registers the type `T` under `name`.
+	/// # Errors
+	/// - [`Error::AlreadyExists`] if `name` or the type `T` is already registered.
+	pub fn register<T: Serial + Any + Clone + Send + Sync>(&mut self, name: &str) -> Result<()> {
+		if self
+			.types
+			.iter()
+			.any(|registered| registered.name.as_ref() == name || registered.type_id == TypeId::of::<T>())
+		{
+			return Err(Error::AlreadyExists { key: name.into() });
+		}
+		self.types.push(Registered {
+			name: name.into(),
+			type_id: TypeId::of::<T>(),
+			get: |board, key| board.get::<T>(key).map(|value| serialize(&value)),
+			set: |board, key, bytes| {
+				let value = deserialize::<T>(key, bytes)?;
+				board
+					.set(key, value)
+					.map(|old| old.map(|old| serialize(&old)))
+			},
+			delete: |board, key| {
+				board
+					.delete::<T>(key)
+					.map(|value| serialize(&value))
+			},
+		});
+		Ok(())
+	}
+
+	/// Returns the name the type `T` is registered with.
+	#[must_use]
+	pub fn name_of<T: Any>(&self) -> Option<&ConstString> {
+		self.types
+			.iter()
+			.find(|registered| registered.type_id == TypeId::of::<T>())
+			.map(|registered| &registered.name)
+	}
+
+	/// Returns the serialized value stored under `key` together with the name of its type.
+	/// # Errors
+	/// - [`Error::Unregistered`] if the type of the entry is not registered.
+	/// - errors of [`DataboardInner::get`]
+	pub fn get(&self, board: &DataboardInner, key: &str) -> Result<(ConstString, Vec<u8>)> {
+		for registered in &self.types {
+			match (registered.get)(board, key) {
+				Ok(bytes) => return Ok((registered.name.clone(), bytes)),
+				Err(Error::WrongType { .. }) => {}
+				Err(error) => return Err(error),
+			}
+		}
+		Err(Error::Unregistered { key: key.into() })
+	}
+
+	/// Stores the serialized value of the type registered as `type_name` under `key`
+	/// and returns the serialized old value.
+	/// # Errors
+	/// - [`Error::Remote`]       if `bytes` are not a valid value.
+	/// - [`Error::Unregistered`] if `type_name` is not registered.
+	/// - errors of [`DataboardInner::set`]
+	pub fn set(&self, board: &DataboardInner, key: &str, type_name: &str, bytes: &[u8]) -> Result<Option<Vec<u8>>> {
+		let registered = self
+			.types
+			.iter()
+			.find(|registered| registered.name.as_ref() == type_name)
+			.ok_or_else(|| Error::Unregistered { key: key.into() })?;
+		(registered.set)(board, key, bytes)
+	}
+
+	/// Deletes the value of the type registered as `type_name` stored under `key` and returns it serialized.
+	/// # Errors
+	/// - [`Error::Unregistered`] if `type_name` is not registered.
+	/// - errors of [`DataboardInner::delete`]
+	pub fn delete(&self, board: &DataboardInner, key: &str, type_name: &str) -> Result<Vec<u8>> {
+		let registered = self
+			.types
+			.iter()
+			.find(|registered| registered.name.as_ref() == type_name)
+			.ok_or_else(|| Error::Unregistered { key: key.into() })?;
+		(registered.delete)(board, key)
+	}
+}
+// endregion:	--- Registry
+
+/// Serializes `value` into a new buffer.
+#[must_use]
+pub fn serialize<T: Serial>(value: &T) -> Vec<u8> {
+	let mut out = Vec::new();
+	value.serialize(&mut out);
+	out
+}
+
+/// Deserializes a value stored under `key` from `bytes`, which must not contain anything else.
+/// # Errors
+/// - [`Error::Remote`] if `bytes` are not a valid value.
+pub fn deserialize<T: Serial>(key: &str, mut bytes: &[u8]) -> Result<T> {
+	match T::deserialize(&mut bytes) {
+		Some(value) if bytes.is_empty() => Ok(value),
+		_ => Err(Error::Remote {
+			message: alloc::format!("malformed value for key {key}").into(),
+		}),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	// check, that the auto traits are available
+	const fn is_normal<T: Sized + Send + Sync>() {}
+
+	#[test]
+	const fn normal_types() {
+		is_normal::<Registry>();
+	}
+
+	fn roundtrip<T: Serial + PartialEq>(value: &T) -> bool {
+		deserialize::<T>("test", &serialize(value)).is_ok_and(|result| &result == value)
+	}
+
+	#[test]
+	fn serial() {
+		assert!(roundtrip(&true));
+		assert!(roundtrip(&-1.5_f32));
+		assert!(roundtrip(&f64::MIN));
+		assert!(roundtrip(&i8::MIN));
+		assert!(roundtrip(&i128::MIN));
+		assert!(roundtrip(&isize::MIN));
+		assert!(roundtrip(&u16::MAX));
+		assert!(roundtrip(&usize::MAX));
+		assert!(roundtrip(&String::from("täst")));
+		assert!(roundtrip(&alloc::vec![String::from("a"), String::new()]));
+		assert!(roundtrip(&Vec::<u8>::new()));
+
+		// malformed values
+		assert!(deserialize::<bool>("test", &[2]).is_err());
+		assert!(deserialize::<u16>("test", &[1]).is_err());
+		assert!(deserialize::<u8>("test", &[1, 2]).is_err());
+		assert!(deserialize::<String>("test", &serialize(&alloc::vec![0xff_u8])).is_err());
+		assert!(deserialize::<Vec<u8>>("test", &serialize(&u64::MAX)).is_err());
+	}
+}
@@ -1,154 +1,380 @@
 // Copyright © 2025 Stephan Kunz
-//! Implementation of the [`DataboardData`].
-
-#![allow(dead_code, unused)]
+//! Implementation of the [`Database`].
 
 use crate::{
 	ConstString, Error,
-	entry::{EntryData, EntryPtr, EntryReadGuard, EntryWriteGuard},
+	atomic::AtomicValue,
+	cloner::CloneRegistry,
+	entry::{EntryData, EntryPtr, EntryReadGuard, EntryUpgradableGuard, EntryWriteGuard},
 	error::Result,
-	remappings::Remappings,
-};
-use alloc::{borrow::ToOwned, boxed::Box, collections::btree_map::BTreeMap, string::String, sync::Arc};
-use core::{
-	any::Any,
-	fmt::Debug,
-	ops::{Deref, DerefMut},
+	journal::{Operation, Recorder},
+	sequence::SequenceId,
+	shard::{Shard, Slot},
 };
+use alloc::{boxed::Box, sync::Arc, vec::Vec};
+use core::any::Any;
 use spin::RwLock;
 
+/// Default number of [`Shard`]s of a [`Database`].
+pub const DEFAULT_SHARDS: usize = 8;
+
 /// Holds all [`Databoard`](crate::databoard::Databoard) data.
-#[derive(Default)]
+///
+/// The entries are distributed over several [`Shard`]s by the hash of their key.
+/// Each [`Shard`] is behind its own `RwLock`, so that operations on keys in different shards,
+/// including the creation and deletion of entries, can proceed in parallel.
 pub struct Database {
-	storage: BTreeMap<ConstString, EntryPtr>,
+	shards: Box<[RwLock<Shard>]>,
+	/// Records all modifications of the database, including those via [`EntryWriteGuard`]s.
+	/// Changes are recorded while the affected shard or entry is locked, so the journal keeps their order.
+	recorder: Arc<Recorder>,
 }
 
 impl core::fmt::Debug for Database {
 	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+		let shards: Vec<_> = self.shards.iter().map(RwLock::read).collect();
+		let mut entries: Vec<_> = shards
+			.iter()
+			.flat_map(|shard| shard.iter())
+			.collect();
+		entries.sort_unstable_by(|a, b| a.0.cmp(b.0));
 		write!(f, "Entries {{ [")?;
 		let mut comma = false;
-		for entry in &self.storage {
+		for (key, slot) in entries {
 			if comma {
 				write!(f, ", ")?;
 			} else {
 				comma = true;
 			}
-			write!(f, "(key: {}", entry.0)?;
-			let data = entry.1.read();
-			write!(f, ", sequence_id: {:?}", data.sequence_id)?;
-			let value = data.data().as_ref();
-			write!(f, ", value: {value:?})")?;
+			write!(f, "(key: {key}, {slot:?})")?;
 		}
 		write!(f, "] }}")
 	}
 }
 
+impl Default for Database {
+	fn default() -> Self {
+		Self::new(DEFAULT_SHARDS)
+	}
+}
+
 impl Database {
+	/// Creates a [`Database`] with the given number of `shards`, which is at least 1.
+	#[must_use]
+	pub fn new(shards: usize) -> Self {
+		Self {
+			shards: (0..shards.max(1))
+				.map(|_| RwLock::new(Shard::default()))
+				.collect(),
+			recorder: Arc::default(),
+		}
+	}
+
+	/// Returns the [`Shard`] responsible for `key`.
+	fn shard(&self, key: &str) -> &RwLock<Shard> {
+		// FNV-1a hash
+		let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+		for byte in key.bytes() {
+			hash ^= u64::from(byte);
+			hash = hash.wrapping_mul(0x0100_0000_01b3);
+		}
+		let index = usize::try_from(hash % self.shards.len() as u64).unwrap_or_default();
+		&self.shards[index]
+	}
+
+	/// Calls `f` with the [`Slot`] stored under `key` and `input`, while holding the read lock of its shard.
+	///
+	/// If `f` can not get the lock of the entry without waiting, it returns the unused `input` as [`Err`].
+	/// Then the shard lock is released and `f` is called again, so the shard is not blocked while waiting.
+	/// Other than waiting on a clone of the [`EntryPtr`], this does not hold an additional reference to the entry,
+	/// which would let a concurrent [`Database::delete`] fail with [`Error::InUse`].
+	fn with_slot<I, R>(
+		&self,
+		key: &str,
+		mut input: I,
+		mut f: impl FnMut(Option<&Slot>, I) -> core::result::Result<R, I>,
+	) -> R {
+		let shard = self.shard(key);
+		loop {
+			match f(shard.read().slot(key), input) {
+				Ok(result) => return result,
+				Err(unused) => input = unused,
+			}
+			core::hint::spin_loop();
+		}
+	}
+
+	/// Swaps `value` into the existing `slot` and records the update, while the entry is locked.
+	/// Returns the unused `value` as [`Err`], if the entry is locked by someone else.
+	/// # Errors
+	/// - [`Error::WrongType`] if the entry has not the expected type `T`.
+	fn try_swap<T: Any + Send + Sync>(
+		&self,
+		key: &ConstString,
+		slot: &Slot,
+		value: T,
+	) -> core::result::Result<Result<T>, T> {
+		match slot {
+			Slot::Atomic(entry) => {
+				let Some(old) = entry.swap(value) else {
+					return Ok(Err(Error::wrong_type::<T>(key.clone(), Some(entry.type_name()))));
+				};
+				self.recorder
+					.record(key, Operation::Update, entry.sequence_id());
+				Ok(Ok(old))
+			}
+			Slot::Locked(entry) => {
+				let Some(mut data) = entry.try_write() else {
+					return Err(value);
+				};
+				let Some(old) = data.swap(value) else {
+					return Ok(Err(Error::wrong_type::<T>(key.clone(), Some(data.type_name))));
+				};
+				self.recorder
+					.record(key, Operation::Update, data.sequence_id);
+				Ok(Ok(old))
+			}
+		}
+	}
+
 	/// Returns `true` if a certain `key` is available, otherwise `false`.
 	#[must_use]
 	pub fn contains_key(&self, key: &str) -> bool {
-		self.storage.contains_key(key)
+		self.shard(key).read().contains_key(key)
 	}
 
 	/// Returns  a result of `true` if a certain `key` of type `T` is available, otherwise a result of `false`.
 	/// # Errors
 	/// - [`Error::WrongType`] if the entry has not the expected type `T`.
 	pub fn contains<T: Any + Send + Sync>(&self, key: &str) -> Result<bool> {
-		if let Some(entry) = self.storage.get(key) {
-			let en = &*entry.read().data;
-			if en.downcast_ref::<T>().is_none() {
-				return Err(Error::WrongType { key: key.into() });
+		let found = self.with_slot(key, (), |slot, ()| match slot {
+			Some(Slot::Atomic(entry)) => Ok(Some((entry.is::<T>(), entry.type_name()))),
+			Some(Slot::Locked(entry)) => entry
+				.try_read()
+				.map(|data| Some((data.data.is::<T>(), data.type_name)))
+				.ok_or(()),
+			None => Ok(None),
+		});
+		let Some((is_t, stored)) = found else {
+			return Ok(false);
+		};
+		if is_t {
+			Ok(true)
+		} else {
+			Err(Error::wrong_type::<T>(key, Some(stored)))
+		}
+	}
+
+	/// Removes all entries.
+	/// Outstanding references to entries stay valid.
+	pub fn clear(&self) {
+		for shard in &self.shards {
+			let mut shard = shard.write();
+			let entries = shard.drain();
+			for key in entries.keys() {
+				self.recorder.record(key, Operation::Delete, 0);
+			}
+			// the removed entries are dropped after releasing the shard lock
+			drop(shard);
+		}
+	}
+
+	/// Removes the entry stored under `key`, if there is one.
+	/// Outstanding references to the entry stay valid.
+	pub fn detach(&self, key: &str) {
+		let mut shard = self.shard(key).write();
+		if shard.remove_entry(key).is_ok() {
+			self.recorder
+				.record(&key.into(), Operation::Delete, 0);
+		}
+	}
+
+	/// Stores a copy of the entry stored under `key` in `source` under the same `key`.
+	/// The copy keeps the sequence id of `source`, also when it replaces an existing entry.
+	/// # Errors
+	/// - [`Error::InUse`]        if the replaced entry is still referenced elsewhere.
+	/// - [`Error::NotFound`]     if `key` is not contained in `source`.
+	/// - [`Error::Unregistered`] if the type of the entry is not registered in `registry`.
+	pub fn copy_from(&self, source: &Self, key: &str, registry: &CloneRegistry) -> Result<()> {
+		// the copy is made before locking the own shard, as `source` may be `self`
+		let slot = source.copy_entry(key, registry)?;
+		let mut shard = self.shard(key).write();
+		let operation = if shard.replace_entry(key.into(), slot)? {
+			Operation::Update
+		} else {
+			Operation::Create
+		};
+		// the new entry can not be locked as nobody else holds a reference yet
+		let sequence_id = shard.slot(key).map_or(0, Slot::sequence_id);
+		self.recorder
+			.record(&key.into(), operation, sequence_id);
+		Ok(())
+	}
+
+	/// Returns an independent copy of the entry stored under `key` including its sequence id.
+	/// # Errors
+	/// - [`Error::NotFound`]     if `key` is not contained.
+	/// - [`Error::Unregistered`] if the type of the entry is not registered in `registry`.
+	fn copy_entry(&self, key: &str, registry: &CloneRegistry) -> Result<Slot> {
+		self.with_slot(key, (), |slot, ()| match slot {
+			Some(Slot::Atomic(entry)) => Ok(Ok(Slot::Atomic(entry.duplicate()))),
+			Some(Slot::Locked(entry)) => {
+				let entry = entry.try_read().ok_or(())?;
+				Ok(registry
+					.clone_value(key, entry.data().as_ref())
+					.map(|data| {
+						let data = EntryData {
+							sequence_id: entry.sequence_id,
+							data,
+							type_name: entry.type_name,
+						};
+						Slot::Locked(Arc::new(RwLock::new(data)))
+					}))
+			}
+			None => Ok(Err(Error::not_found(key))),
+		})
+	}
+
+	/// Returns an independent copy of the database with the same entries and sequence ids.
+	/// The journal of the copy starts empty.
+	/// # Errors
+	/// - [`Error::Unregistered`] if the type of an entry is not registered in `registry`.
+	pub fn deep_clone(&self, registry: &CloneRegistry) -> Result<Self> {
+		let copy = Self::new(self.shards.len());
+		for key in self.keys() {
+			match self.copy_entry(&key, registry) {
+				Ok(slot) => copy.shard(&key).write().insert_entry(key, slot)?,
+				// deleted in the meantime
+				Err(Error::NotFound { .. }) => {}
+				Err(error) => return Err(error),
 			}
-			return Ok(true);
 		}
-		Ok(false)
+		Ok(copy)
 	}
 
 	/// Creates a value of type `T` under `key`.
 	/// # Errors
 	/// - [`Error::AlreadyExists`] if `key` already exists.
-	pub fn create<T: Any + Send + Sync>(&mut self, key: impl Into<ConstString>, value: T) -> Result<()> {
+	pub fn create<T: Any + Send + Sync>(&self, key: impl Into<ConstString>, value: T) -> Result<()> {
 		let key = key.into();
-		if self.storage.contains_key(&key) {
+		let mut shard = self.shard(&key).write();
+		if shard
+			.get_or_create(key.clone(), value, Slot::locked)
+			.is_some()
+		{
 			return Err(Error::AlreadyExists { key });
 		}
-
-		let entry = Arc::new(RwLock::new(EntryData::new(value)));
-		if self.storage.insert(key, entry).is_some() {
-			return Err(Error::Unreachable(file!().into(), line!()));
-		}
+		self.recorder.record(&key, Operation::Create, 1);
 		Ok(())
 	}
 
-	/// Returns the value of type `T` stored under `key` and deletes it from storage.
+	/// Returns the value of type `T` stored under `key` and deletes it.
+	///
+	/// An entry can only be deleted if nobody else holds a reference to it,
+	/// e.g. an [`EntryPtr`], an [`EntryReadGuard`] or an [`EntryWriteGuard`].
+	/// Otherwise the entry is left untouched and [`Error::InUse`] is returned.
 	/// # Errors
+	/// - [`Error::InUse`]     if the entry is still referenced elsewhere.
 	/// - [`Error::NotFound`]  if `key` is not contained.
 	/// - [`Error::WrongType`] if the entry has not the expected type `T`.
-	pub fn delete<T: Any + Send + Sync>(&mut self, key: &str) -> Result<T> {
-		// check type
-		if let Some(entry) = self.storage.get(key) {
-			let en = &*entry.read().data;
-			if entry.read().data.downcast_ref::<T>().is_none() {
-				return Err(Error::WrongType { key: key.into() });
-			}
-		} else {
-			return Err(Error::NotFound { key: key.into() });
-		}
-		if let Some(old) = self.storage.remove(key)
-			&& let Some(entry) = Arc::into_inner(old)
-		{
-			let entry_data = entry.into_inner(); // will block, if the RwLock is locked
-			match entry_data.data.downcast::<T>() {
-				Ok(t) => return Ok(*t),
-				Err(_) => return Err(Error::WrongType { key: key.into() }),
-			}
-		}
-
-		// We should never reach this!
-		Err(Error::Unreachable(file!().into(), line!()))
+	pub fn delete<T: Any + Send + Sync>(&self, key: &str) -> Result<T> {
+		let mut shard = self.shard(key).write();
+		let value = shard.delete(key)?;
+		self.recorder
+			.record(&key.into(), Operation::Delete, 0);
+		Ok(value)
 	}
 
 	/// Returns a clone of the [`EntryPtr`]
 	/// # Errors
+	/// - [`Error::LockFree`] if the entry is stored lock-free.
 	/// - [`Error::NotFound`] if `key` is not contained.
 	pub fn entry(&self, key: &str) -> Result<EntryPtr> {
-		if let Some(entry) = self.storage.get(key) {
-			return Ok(entry.clone());
-		}
-
-		Err(Error::NotFound { key: key.into() })
+		self.shard(key).read().entry(key)
 	}
 
 	/// Returns a read/write guard to the `T` of the `entry` stored under `key`.
-	/// The entry is locked for read & write while this reference is held.
-	/// Multiple changes during holding the reference are counted as a single change,
-	/// so `sequence_id()`will only increase by 1.
-	///
-	/// You need to drop the received [`EntryWriteGuard`] before using `delete`, `read`, `update` or `sequence_id`.
 	/// # Errors
+	/// - [`Error::LockFree`]  if the entry is stored lock-free.
 	/// - [`Error::NotFound`]  if `key` is not contained.
 	/// - [`Error::WrongType`] if the entry has not the expected type `T`.
 	pub fn get_mut_ref<T: Any + Send + Sync>(&self, key: &str) -> Result<EntryWriteGuard<T>> {
-		if let Some(entry) = self.storage.get(key) {
-			return EntryWriteGuard::new(key, entry);
-		}
-
-		Err(Error::NotFound { key: key.into() })
+		// the shard lock must not be held while waiting for the entry lock
+		let entry = self.entry(key)?;
+		Ok(EntryWriteGuard::<T>::new(key, &entry)?.tracked(&self.recorder, key))
 	}
 
 	/// Returns a read guard to the `T` of the `entry` stored under `key`.
-	/// The entry is locked for write while this reference is held.
-	///
-	/// You need to drop the received [`EntryReadGuard`] before using `delete`, or `update`.
 	/// # Errors
+	/// - [`Error::LockFree`]  if the entry is stored lock-free.
 	/// - [`Error::NotFound`]  if `key` is not contained.
 	/// - [`Error::WrongType`] if the entry has not the expected type `T`.
 	pub fn get_ref<T: Any + Send + Sync>(&self, key: &str) -> Result<EntryReadGuard<T>> {
-		if let Some(entry) = self.storage.get(key) {
-			return EntryReadGuard::new(key, entry.clone());
-		}
+		// the shard lock must not be held while waiting for the entry lock
+		let entry = self.entry(key)?;
+		EntryReadGuard::new(key, entry)
+	}
 
-		Err(Error::NotFound { key: key.into() })
+	/// Returns an upgradable read guard to the `T` of the `entry` stored under `key`.
+	/// # Errors
+	/// - [`Error::LockFree`]  if the entry is stored lock-free.
+	/// - [`Error::NotFound`]  if `key` is not contained.
+	/// - [`Error::WrongType`] if the entry has not the expected type `T`.
+	pub fn get_upgradable_ref<T: Any + Send + Sync>(&self, key: &str) -> Result<EntryUpgradableGuard<T>> {
+		// the shard lock must not be held while waiting for the entry lock
+		let entry = self.entry(key)?;
+		Ok(EntryUpgradableGuard::<T>::new(key, entry)?.tracked(&self.recorder, key))
+	}
+
+	/// Calls `f` with the sequence id and the type erased value of the entry stored under `key`.
+	///
+	/// `f` is called while the shard of `key` is read locked, so it must not modify this database.
+	/// # Errors
+	/// - [`Error::NotFound`] if `key` is not contained.
+	pub fn inspect<R>(&self, key: &str, f: impl FnOnce(SequenceId, &(dyn Any + Send + Sync)) -> R) -> Result<R> {
+		self.with_slot(key, f, |slot, f| match slot {
+			Some(Slot::Atomic(entry)) => Ok(Ok(f(entry.sequence_id(), entry.boxed().as_ref()))),
+			Some(Slot::Locked(entry)) => match entry.try_read() {
+				Some(data) => Ok(Ok(f(data.sequence_id, data.data().as_ref()))),
+				None => Err(f),
+			},
+			None => Ok(Err(Error::not_found(key))),
+		})
+	}
+
+	/// Returns `true` if the database contains no entries, otherwise `false`.
+	#[must_use]
+	pub fn is_empty(&self) -> bool {
+		self.shards
+			.iter()
+			.all(|shard| shard.read().is_empty())
+	}
+
+	/// Returns the sorted keys of all entries in the database.
+	#[must_use]
+	pub fn keys(&self) -> Vec<ConstString> {
+		let mut keys: Vec<ConstString> = self
+			.shards
+			.iter()
+			.flat_map(|shard| {
+				shard
+					.read()
+					.iter()
+					.map(|(key, _)| key.clone())
+					.collect::<Vec<_>>()
+			})
+			.collect();
+		keys.sort_unstable();
+		keys
+	}
+
+	/// Returns the number of entries in the database.
+	#[must_use]
+	pub fn len(&self) -> usize {
+		self.shards
+			.iter()
+			.map(|shard| shard.read().len())
+			.sum()
 	}
 
 	/// Returns a copy of the value of type `T` stored under `key`.
@@ -156,61 +382,216 @@ impl Database {
 	/// - [`Error::NotFound`]  if `key` is not contained.
 	/// - [`Error::WrongType`] if the entry has not the expected type `T`.
 	pub fn read<T: Any + Clone + Send + Sync>(&self, key: &str) -> Result<T> {
-		self.storage.get(key).map_or_else(
-			|| Err(Error::NotFound { key: key.into() }),
-			|entry| {
-				let en = &*entry.read().data;
-				let t = en.downcast_ref::<T>();
-				t.cloned()
-					.map_or_else(|| Err(Error::WrongType { key: key.into() }), |v| Ok(v))
-			},
-		)
+		self.with_slot(key, (), |slot, ()| match slot {
+			Some(Slot::Atomic(entry)) => Ok(entry
+				.load::<T>()
+				.ok_or_else(|| Error::wrong_type::<T>(key, Some(entry.type_name())))),
+			Some(Slot::Locked(entry)) => entry
+				.try_read()
+				.map(|data| {
+					data.data
+						.downcast_ref::<T>()
+						.cloned()
+						.ok_or_else(|| Error::wrong_type::<T>(key, Some(data.type_name)))
+				})
+				.ok_or(()),
+			None => Ok(Err(Error::not_found(key))),
+		})
+	}
+
+	/// Returns the type erased value stored under `key` and deletes it.
+	/// # Errors
+	/// - [`Error::InUse`]     if the entry is still referenced elsewhere.
+	/// - [`Error::NotFound`]  if `key` is not contained.
+	pub fn remove(&self, key: &str) -> Result<Box<dyn Any + Send + Sync>> {
+		let mut shard = self.shard(key).write();
+		let value = shard.remove(key)?;
+		self.recorder
+			.record(&key.into(), Operation::Delete, 0);
+		Ok(value)
+	}
+
+	/// Returns the [`Recorder`] of the database.
+	#[must_use]
+	pub fn recorder(&self) -> &Recorder {
+		&self.recorder
+	}
+
+	/// Stores `value` under `key` and returns an eventually existing old value of type `T`.
+	/// A non existing entry is created in a single step, so concurrent callers can not create it twice.
+	/// # Errors
+	/// - [`Error::WrongType`] if `key` already exists with a different type.
+	pub fn set<T: Any + Send + Sync>(&self, key: impl Into<ConstString>, value: T) -> Result<Option<T>> {
+		self.set_with(key, value, Slot::locked)
+	}
+
+	/// Stores `value` under `key` and returns an eventually existing old value of type `T`.
+	/// A non existing entry is created lock-free in a single step, so concurrent callers can not create it twice.
+	/// An already existing entry is updated the way it is stored.
+	/// # Errors
+	/// - [`Error::WrongType`] if `key` already exists with a different type.
+	pub fn set_atomic<T: AtomicValue>(&self, key: impl Into<ConstString>, value: T) -> Result<Option<T>> {
+		self.set_with(key, value, Slot::atomic)
+	}
+
+	/// Implementation of [`Database::set`] and [`Database::set_atomic`],
+	/// `create` determines how a non existing entry is stored.
+	///
+	/// An existing entry is updated holding only the read lock of its shard,
+	/// the write lock is only taken to create a new entry.
+	fn set_with<T: Any + Send + Sync>(
+		&self,
+		key: impl Into<ConstString>,
+		mut value: T,
+		create: impl Fn(T) -> Slot,
+	) -> Result<Option<T>> {
+		let key = key.into();
+		let shard = self.shard(&key);
+		loop {
+			let attempt = {
+				let guard = shard.read();
+				if let Some(slot) = guard.slot(&key) {
+					self.try_swap(&key, slot, value)
+				} else {
+					drop(guard);
+					// the entry may have been created meanwhile
+					let mut guard = shard.write();
+					match guard.get_or_create(key.clone(), value, &create) {
+						None => {
+							self.recorder.record(&key, Operation::Create, 1);
+							return Ok(None);
+						}
+						Some((slot, value)) => self.try_swap(&key, slot, value),
+					}
+				}
+			};
+			match attempt {
+				Ok(old) => return old.map(Some),
+				// the shard lock must not be held while waiting for the entry lock
+				Err(unused) => value = unused,
+			}
+			core::hint::spin_loop();
+		}
 	}
 
 	/// Returns the sequence id of an entry.
-	/// The sequence id starts with '1' and is increased at every change of an entry.
-	/// The sequence wraps around to '1' after reaching [`usize::MAX`] .
 	/// # Errors
 	/// - [`Error::NotFound`] if `key` is not contained.
-	pub fn sequence_id(&self, key: &str) -> Result<usize> {
-		self.storage.get(key).map_or_else(
-			|| Err(Error::NotFound { key: key.into() }),
-			|entry| Ok(entry.read().sequence_id),
-		)
+	pub fn sequence_id(&self, key: &str) -> Result<SequenceId> {
+		self.with_slot(key, (), |slot, ()| {
+			slot.map_or_else(
+				|| Ok(Err(Error::not_found(key))),
+				|slot| slot.try_sequence_id().map(Ok).ok_or(()),
+			)
+		})
+	}
+
+	/// Transfers the entry stored under `key` to `new_key` of the `target` [`Database`].
+	/// The transfer is atomic, both involved shards are locked during the whole operation.
+	/// # Errors
+	/// - [`Error::AlreadyExists`] if `new_key` already exists in `target`.
+	/// - [`Error::NotFound`]      if `key` is not contained.
+	pub fn transfer(&self, key: &str, target: &Self, new_key: ConstString) -> Result<()> {
+		let source_shard = self.shard(key);
+		let target_shard = target.shard(&new_key);
+		// the changes are recorded while the shards are locked, so the journals keep their order
+		loop {
+			let moved = if core::ptr::eq(source_shard, target_shard) {
+				let mut shard = source_shard.write();
+				if shard.contains_key(&new_key) {
+					return Err(Error::AlreadyExists { key: new_key });
+				}
+				let sequence_id = Self::try_sequence_id(&shard, key)?;
+				if let Some(sequence_id) = sequence_id {
+					let slot = shard.remove_entry(key)?;
+					shard.insert_entry(new_key.clone(), slot)?;
+					self.record_transfer(key, target, &new_key, sequence_id);
+				}
+				sequence_id.is_some()
+			} else {
+				// always lock in the same order to avoid deadlocks
+				let (mut from, mut to) = if core::ptr::from_ref(source_shard) < core::ptr::from_ref(target_shard) {
+					let from = source_shard.write();
+					(from, target_shard.write())
+				} else {
+					let to = target_shard.write();
+					(source_shard.write(), to)
+				};
+				if to.contains_key(&new_key) {
+					return Err(Error::AlreadyExists { key: new_key });
+				}
+				let sequence_id = Self::try_sequence_id(&from, key)?;
+				if let Some(sequence_id) = sequence_id {
+					let slot = from.remove_entry(key)?;
+					to.insert_entry(new_key.clone(), slot)?;
+					self.record_transfer(key, target, &new_key, sequence_id);
+				}
+				sequence_id.is_some()
+			};
+			if moved {
+				return Ok(());
+			}
+			// the shard locks must not be held while waiting for the entry lock
+			core::hint::spin_loop();
+		}
+	}
+
+	/// Records the transfer of the entry stored under `key` to `new_key` of the `target` [`Database`].
+	fn record_transfer(&self, key: &str, target: &Self, new_key: &ConstString, sequence_id: SequenceId) {
+		self.recorder
+			.record(&key.into(), Operation::Delete, 0);
+		target
+			.recorder
+			.record(new_key, Operation::Create, sequence_id);
+	}
+
+	/// Returns the sequence id of the entry stored under `key` in `shard`,
+	/// or `None` if the entry is write locked by someone else.
+	/// # Errors
+	/// - [`Error::NotFound`] if `key` is not contained.
+	fn try_sequence_id(shard: &Shard, key: &str) -> Result<Option<SequenceId>> {
+		shard
+			.slot(key)
+			.map(Slot::try_sequence_id)
+			.ok_or_else(|| Error::not_found(key))
 	}
 
 	/// Returns a read/write guard to the `T` of the `entry` stored under `key`.
-	/// The entry is locked for read & write while this reference is held.
-	/// Multiple changes during holding the reference are counted as a single change,
-	/// so `sequence_id()`will only increase by 1.
-	///
-	/// You need to drop the received [`EntryWriteGuard`] before using `delete`, `read`, `update` or `sequence_id`.
 	/// # Errors
 	/// - [`Error::IsLocked`]  if the entry is locked by someone else.
+	/// - [`Error::LockFree`]  if the entry is stored lock-free.
 	/// - [`Error::NotFound`]  if `key` is not contained.
 	/// - [`Error::WrongType`] if the entry has not the expected type `T`.
 	pub fn try_get_mut_ref<T: Any + Send + Sync>(&self, key: &str) -> Result<EntryWriteGuard<T>> {
-		if let Some(entry) = self.storage.get(key) {
-			return EntryWriteGuard::try_new(key, entry);
-		}
-
-		Err(Error::NotFound { key: key.into() })
+		Ok(self
+			.shard(key)
+			.read()
+			.try_get_mut_ref::<T>(key)?
+			.tracked(&self.recorder, key))
 	}
 
 	/// Returns a read guard to the `T` of the `entry` stored under `key`.
-	/// The entry is locked for write while this reference is held.
-	///
-	/// You need to drop the received [`EntryReadGuard`] before using `delete`, or `update`.
 	/// # Errors
 	/// - [`Error::IsLocked`]  if the entry is write locked by someone else.
+	/// - [`Error::LockFree`]  if the entry is stored lock-free.
 	/// - [`Error::NotFound`]  if `key` is not contained.
 	/// - [`Error::WrongType`] if the entry has not the expected type `T`.
 	pub fn try_get_ref<T: Any + Send + Sync>(&self, key: &str) -> Result<EntryReadGuard<T>> {
-		if let Some(entry) = self.storage.get(key) {
-			return EntryReadGuard::try_new(key, entry);
-		}
+		self.shard(key).read().try_get_ref(key)
+	}
 
-		Err(Error::NotFound { key: key.into() })
+	/// Returns the name of the type stored under `key`.
+	/// # Errors
+	/// - [`Error::NotFound`] if `key` is not contained.
+	pub fn type_name(&self, key: &str) -> Result<&'static str> {
+		self.with_slot(key, (), |slot, ()| match slot {
+			Some(Slot::Atomic(entry)) => Ok(Ok(entry.type_name())),
+			Some(Slot::Locked(entry)) => entry
+				.try_read()
+				.map(|data| Ok(data.type_name))
+				.ok_or(()),
+			None => Ok(Err(Error::not_found(key))),
+		})
 	}
 
 	/// Updates a value of type `T` stored under `key` and returns the old value.
@@ -218,24 +599,13 @@ impl Database {
 	/// - [`Error::NotFound`]  if `key` is not contained.
 	/// - [`Error::WrongType`] if the entry has not the expected type `T`.
 	pub fn update<T: Any + Send + Sync>(&self, key: &str, value: T) -> Result<T> {
-		let mut value = value;
-		self.storage.get(key).map_or_else(
-			|| Err(Error::NotFound { key: key.into() }),
-			|entry| {
-				let en = &mut *entry.write();
-				if let Some(t) = en.data.downcast_mut::<T>() {
-					core::mem::swap(t, &mut value);
-					if en.sequence_id < usize::MAX {
-						en.sequence_id += 1;
-					} else {
-						en.sequence_id = 1;
-					}
-					Ok(value)
-				} else {
-					Err(Error::WrongType { key: key.into() })
-				}
-			},
-		)
+		let key = ConstString::from(key);
+		self.with_slot(&key, value, |slot, value| {
+			let Some(slot) = slot else {
+				return Ok(Err(Error::not_found(key.clone())));
+			};
+			self.try_swap(&key, slot, value)
+		})
 	}
 }
 
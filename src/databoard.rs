@@ -5,14 +5,27 @@
 extern crate std;
 
 use crate::{
-	ConstString, Error, check_board_pointer, check_top_level_key,
-	database::Database,
-	entry::{EntryPtr, EntryReadGuard, EntryWriteGuard},
+	ConstString, Error,
+	atomic::AtomicValue,
+	backoff::Backoff,
+	check_board_pointer, check_top_level_key,
+	cloner::CloneRegistry,
+	database::{DEFAULT_SHARDS, Database},
+	defaults::Defaults,
+	diff::{Diff, MergePolicy},
+	entry::{EntryPtr, EntryReadGuard, EntryUpgradableGuard, EntryWriteGuard},
 	error::Result,
+	journal::Changes,
 	remappings::{Remappings, check_local_key},
+	sequence::SequenceId,
 	strip_board_pointer,
+	view::DataboardView,
+};
+use alloc::{
+	boxed::Box,
+	sync::{Arc, Weak},
+	vec::Vec,
 };
-use alloc::sync::Arc;
 use core::{any::Any, ops::Deref};
 use spin::RwLock;
 
@@ -29,7 +42,7 @@ impl core::fmt::Debug for Databoard {
 	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
 		write!(f, "Databoard {{ ")?;
 		write!(f, "autoremap: {:?}", &self.0.autoremap)?;
-		write!(f, ", {:?}", &*self.0.database.read())?;
+		write!(f, ", {:?}", &self.0.database)?;
 		write!(f, ", {:?}", &self.0.remappings)?;
 		write!(f, ", parent: ")?;
 		if let Some(parent) = &self.0.parent {
@@ -52,10 +65,12 @@ impl Deref for Databoard {
 impl Default for Databoard {
 	fn default() -> Self {
 		Self(Arc::new(DataboardInner {
-			database: RwLock::new(Database::default()),
+			database: Database::default(),
+			defaults: Defaults::default(),
 			parent: None,
 			remappings: Remappings::default(),
 			autoremap: false,
+			children: RwLock::default(),
 		}))
 	}
 }
@@ -68,28 +83,155 @@ impl Databoard {
 	}
 
 	/// Creates a [`Databoard`] with given parameters.
+	#[must_use]
 	pub fn with(parent: Option<Self>, remappings: Option<Remappings>, autoremap: bool) -> Self {
+		Self::with_shards(parent, remappings, autoremap, DEFAULT_SHARDS)
+	}
+
+	/// Creates a [`Databoard`] with given parameters, distributing its entries over the given number of `shards`.
+	///
+	/// Operations on entries in different shards do not block each other,
+	/// a single shard minimizes the memory footprint.
+	#[must_use]
+	pub fn with_shards(parent: Option<Self>, remappings: Option<Remappings>, autoremap: bool, shards: usize) -> Self {
 		let remappings = remappings.map_or_else(Remappings::default, |remappings| remappings);
-		let database = RwLock::new(Database::default());
+		let database = Database::new(shards);
 		Self(Arc::new(DataboardInner {
 			database,
+			defaults: Defaults::default(),
+			parent,
+			remappings,
+			autoremap,
+			children: RwLock::default(),
+		}))
+		.registered()
+	}
+
+	/// Creates a [`Databoard`] with given parameters and a table of `defaults`,
+	/// which is consulted by `get` for missing entries.
+	#[must_use]
+	pub fn with_defaults(parent: Option<Self>, remappings: Option<Remappings>, autoremap: bool, defaults: Defaults) -> Self {
+		let remappings = remappings.map_or_else(Remappings::default, |remappings| remappings);
+		Self(Arc::new(DataboardInner {
+			database: Database::default(),
+			defaults,
 			parent,
 			remappings,
 			autoremap,
+			children: RwLock::default(),
 		}))
+		.registered()
 	}
 
 	/// Creates a [`Databoard`] using the given parent.
 	/// The parents entries are automatically remapped into the new databoard.
 	#[must_use]
 	pub fn with_parent(parent: Self) -> Self {
-		let database = RwLock::new(Database::default());
+		let database = Database::default();
 		Self(Arc::new(DataboardInner {
 			database,
+			defaults: Defaults::default(),
 			parent: Some(parent),
 			remappings: Remappings::default(),
 			autoremap: true,
+			children: RwLock::default(),
+		}))
+		.registered()
+	}
+
+	/// Creates a [`Databoard`] like [`Databoard::with`] using `autoremap`,
+	/// which is not registered as a child of its `parent`, so it is invisible to the hierarchy.
+	pub(crate) fn detached(parent: Self, remappings: Option<Remappings>) -> Self {
+		let remappings = remappings.map_or_else(Remappings::default, |remappings| remappings);
+		Self(Arc::new(DataboardInner {
+			database: Database::new(1),
+			defaults: Defaults::default(),
+			parent: Some(parent),
+			remappings,
+			autoremap: true,
+			children: RwLock::default(),
+		}))
+	}
+
+	/// Returns an independent copy of the [`Databoard`] with the same entries, sequence ids, remappings and defaults.
+	/// The copy has the same parent as the original, its journal starts empty.
+	/// # Errors
+	/// - [`Error::Unregistered`] if the type of an entry is not registered in `registry`.
+	pub fn deep_clone(&self, registry: &CloneRegistry) -> Result<Self> {
+		self.copy(self.parent.clone(), registry)
+	}
+
+	/// Returns an independent copy of the [`Databoard`] like [`Databoard::deep_clone`],
+	/// but also its parents are copied, so the whole hierarchy above the copy is independent of the original.
+	/// # Errors
+	/// - [`Error::Unregistered`] if the type of an entry is not registered in `registry`.
+	pub fn fork(&self, registry: &CloneRegistry) -> Result<Self> {
+		let parent = self
+			.parent
+			.as_ref()
+			.map(|parent| parent.fork(registry))
+			.transpose()?;
+		self.copy(parent, registry)
+	}
+
+	/// Creates a copy of the [`Databoard`] with the given `parent`.
+	fn copy(&self, parent: Option<Self>, registry: &CloneRegistry) -> Result<Self> {
+		Ok(Self(Arc::new(DataboardInner {
+			database: self.database.deep_clone(registry)?,
+			defaults: self.defaults.deep_clone(registry)?,
+			parent,
+			remappings: self.remappings.clone(),
+			autoremap: self.autoremap,
+			children: RwLock::default(),
 		}))
+		.registered())
+	}
+
+	/// Returns a read-only [`DataboardView`] of the [`Databoard`].
+	#[must_use]
+	pub fn view(&self) -> DataboardView {
+		DataboardView::from(self)
+	}
+
+	/// Registers the [`Databoard`] as a child at its parent.
+	/// Children are only tracked weakly, so a parent never keeps its children alive.
+	fn registered(self) -> Self {
+		if let Some(parent) = &self.0.parent {
+			let mut children = parent.children.write();
+			// remove already dropped children
+			children.retain(|child| child.strong_count() > 0);
+			children.push(Arc::downgrade(&self.0));
+		}
+		self
+	}
+
+	/// Returns the currently alive direct children of the [`Databoard`].
+	#[must_use]
+	pub fn children(&self) -> Vec<Self> {
+		self.0
+			.children
+			.read()
+			.iter()
+			.filter_map(Weak::upgrade)
+			.map(Self)
+			.collect()
+	}
+
+	/// Visits the [`Databoard`] and all its alive descendants depth first.
+	/// The closure gets each visited [`Databoard`] together with its depth relative to `self`.
+	///
+	/// No lock is held while calling `f`, so it is possible to operate on the visited boards,
+	/// e.g. to collect statistics or to clear local entries.
+	pub fn visit<F: FnMut(&Self, usize)>(&self, mut f: F) {
+		self.visit_inner(&mut f, 0);
+	}
+
+	/// Recursive part of [`Databoard::visit`].
+	fn visit_inner<F: FnMut(&Self, usize)>(&self, f: &mut F, depth: usize) {
+		f(self, depth);
+		for child in self.children() {
+			child.visit_inner(f, depth + 1);
+		}
 	}
 }
 
@@ -97,24 +239,75 @@ impl Databoard {
 #[derive(Default)]
 pub struct DataboardInner {
 	/// database of this `Databoard`.
-	/// It is behind an `RwLock` to protect against data races.
-	database: RwLock<Database>,
+	/// It is internally synchronized to protect against data races.
+	pub(crate) database: Database,
+	/// Default values used by `get` for missing entries.
+	defaults: Defaults,
 	/// An optional reference to a parent `Databoard`.
 	parent: Option<Databoard>,
 	/// Manual remapping rules from this `Databoard` to the parent.
 	remappings: Remappings,
 	/// Whether to use automatic remapping to parents content.
 	autoremap: bool,
+	/// Weak references to the children of this `Databoard`.
+	children: RwLock<Vec<Weak<DataboardInner>>>,
 }
 
 impl DataboardInner {
+	/// Returns the changes of the entries of the [`Databoard`] itself recorded since `cursor`,
+	/// together with the cursor for the next call.
+	///
+	/// Changes are only recorded while the journal is enabled with [`DataboardInner::set_journal_capacity`].
+	/// If changes since `cursor` have already been dropped from the journal, the result is marked as not complete
+	/// and the consumer has to resynchronize the whole board.
+	/// To do so, it should fetch the cursor via [`DataboardInner::journal_cursor`] before reading the entries.
+	#[must_use]
+	pub fn changes_since(&self, cursor: u64) -> Changes {
+		self.database.recorder().changes_since(cursor)
+	}
+
+	/// Removes all entries from the [`Databoard`] itself and the entries of parents its remappings point to.
+	/// Other entries of parents and the entries of children are not touched, see [`DataboardInner::clear_subtree`].
+	/// Entries still referenced elsewhere are detached from the [`Databoard`], but those references stay valid.
+	pub fn clear(&self) {
+		self.clear_local();
+		for (key, _) in self.remappings.iter() {
+			// assignments and missing parents have nothing to clear
+			if let Ok((board, key)) = self.resolve(key) {
+				board.database.detach(&key);
+			}
+		}
+	}
+
+	/// Removes all entries from the [`Databoard`] itself.
+	/// Neither entries of parents nor entries of children are touched.
+	/// Entries still referenced elsewhere are detached from the [`Databoard`], but those references stay valid.
+	pub fn clear_local(&self) {
+		self.database.clear();
+	}
+
+	/// Removes all entries from the [`Databoard`] itself and all its alive descendants.
+	/// Entries of parents are not touched.
+	/// Entries still referenced elsewhere are detached from the [`Databoard`], but those references stay valid.
+	pub fn clear_subtree(&self) {
+		self.clear_local();
+		for child in self
+			.children
+			.read()
+			.iter()
+			.filter_map(Weak::upgrade)
+		{
+			child.clear_subtree();
+		}
+	}
+
 	/// Returns `true` if a certain `key` is available, otherwise `false`.
 	#[must_use]
 	pub fn contains_key(&self, key: &str) -> bool {
 		match check_top_level_key(key) {
 			Ok(stripped_key) => self.root().contains_key(stripped_key),
 			Err(original_key) => match check_local_key(original_key) {
-				Ok(local_key) => self.database.read().contains_key(local_key),
+				Ok(local_key) => self.database.contains_key(local_key),
 				Err(original_key) => {
 					let (parent_key, has_remapping) = self.remapping_info(original_key);
 					if has_remapping {
@@ -131,7 +324,7 @@ impl DataboardInner {
 						parent.contains_key(&parent_key)
 					} else {
 						// No remapping, use local database
-						self.database.read().contains_key(original_key)
+						self.database.contains_key(original_key)
 					}
 				}
 			},
@@ -143,10 +336,10 @@ impl DataboardInner {
 	/// - [`Error::NoParent`]  if `key` is remapped to a parent without having a parent.
 	/// - [`Error::WrongType`] if the entry has not the expected type `T`.
 	pub fn contains<T: Any + Send + Sync>(&self, key: &str) -> Result<bool> {
-		match check_top_level_key(key) {
+		let result = match check_top_level_key(key) {
 			Ok(stripped_key) => self.root().contains::<T>(stripped_key),
 			Err(original_key) => match check_local_key(original_key) {
-				Ok(local_key) => self.database.read().contains::<T>(local_key),
+				Ok(local_key) => self.database.contains::<T>(local_key),
 				Err(original_key) => {
 					let (parent_key, has_remapping) = self.remapping_info(original_key);
 					if has_remapping {
@@ -167,31 +360,72 @@ impl DataboardInner {
 						parent.contains::<T>(&parent_key)
 					} else {
 						// No remapping, use local database
-						self.database.read().contains::<T>(original_key)
+						self.database.contains::<T>(original_key)
 					}
 				}
 			},
-		}
+		};
+		result.map_err(|error| self.locate(key, error))
+	}
+
+	/// Creates the value of type `T` under `key`.
+	///
+	/// In contrast to `set` an already existing entry is not touched,
+	/// so this can be used to ensure that `key` did not exist before.
+	/// # Errors
+	/// - [`Error::AlreadyExists`] if `key` already exists.
+	/// - [`Error::Assignment`]    if the remapping contains an assignment of a `str` value.
+	/// - [`Error::NoParent`]      if `key` is remapped to a parent without having a parent.
+	pub fn create<T: Any + Send + Sync>(&self, key: &str, value: T) -> Result<()> {
+		let (board, key) = self.resolve(key)?;
+		board.database.create(key, value)
 	}
 
-	/// Prints the content of the [`Databoard`] for debugging purpose.
+	/// Creates the value of type `T` under `key` in the [`Databoard`] itself.
+	/// Remappings are not evaluated, a leading `_` of `key` is optional.
+	/// # Errors
+	/// - [`Error::AlreadyExists`] if `key` already exists in the [`Databoard`].
+	pub fn create_local<T: Any + Send + Sync>(&self, key: &str, value: T) -> Result<()> {
+		let key = check_local_key(key).unwrap_or(key);
+		self.database.create(key, value)
+	}
+
+	/// Prints the content of the [`Databoard`] and all its alive descendants for debugging purpose.
 	#[cfg(feature = "std")]
 	pub fn debug_message(&self) {
-		let _ = self.parent;
-		std::println!("not yet implemented");
+		std::println!("level {}: {:?}, {:?}", self.level(), &self.database, &self.remappings);
+		for child in self
+			.children
+			.read()
+			.iter()
+			.filter_map(Weak::upgrade)
+		{
+			child.debug_message();
+		}
+	}
+
+	/// Returns the table of default values of the [`Databoard`].
+	#[must_use]
+	pub const fn defaults(&self) -> &Defaults {
+		&self.defaults
 	}
 
 	/// Returns the value of type `T` stored under `key` and deletes it from database.
+	///
+	/// An entry can only be deleted if nobody else holds a reference to it,
+	/// e.g. an [`EntryPtr`], an [`EntryReadGuard`] or an [`EntryWriteGuard`].
+	/// Otherwise the entry is left untouched and [`Error::InUse`] is returned.
 	/// # Errors
 	/// - [`Error::Assignment`] if the remapping contains an assignment of a `str` value.
+	/// - [`Error::InUse`]      if the entry is still referenced elsewhere.
 	/// - [`Error::NoParent`]   if `key` is remapped to a parent without having a parent.
 	/// - [`Error::NotFound`]   if `key` is not contained.
 	/// - [`Error::WrongType`]  if the entry has not the expected type `T`.
 	pub fn delete<T: Any + Send + Sync>(&self, key: &str) -> Result<T> {
-		match check_top_level_key(key) {
+		let result = match check_top_level_key(key) {
 			Ok(stripped_key) => self.root().delete(stripped_key),
 			Err(original_key) => match check_local_key(original_key) {
-				Ok(local_key) => self.database.write().delete(local_key),
+				Ok(local_key) => self.database.delete(local_key),
 				Err(original_key) => {
 					let (parent_key, has_remapping) = self.remapping_info(original_key);
 					if has_remapping {
@@ -220,23 +454,64 @@ impl DataboardInner {
 						parent.delete(&parent_key)
 					} else {
 						// No remapping, use local database
-						self.database.write().delete(original_key)
+						self.database.delete(original_key)
 					}
 				}
 			},
+		};
+		result.map_err(|error| self.locate(key, error))
+	}
+
+	/// Returns the differences of the entries stored in `other` itself compared to the entries stored in the
+	/// [`Databoard`] itself. Entries of parents and defaults are not taken into account.
+	///
+	/// An entry counts as changed if the types differ, if the values differ for a type registered with
+	/// [`CloneRegistry::register_comparable`], or otherwise if the sequence ids differ.
+	#[must_use]
+	pub fn diff(&self, other: &Self, registry: &CloneRegistry) -> Diff {
+		let own = self.keys();
+		let others = other.keys();
+		let mut diff = Diff::default();
+		for key in &own {
+			if others.binary_search(key).is_err() {
+				diff.removed.push(key.clone());
+			} else if self.differs(other, key, registry) {
+				diff.changed.push(key.clone());
+			}
 		}
+		diff.added = others
+			.into_iter()
+			.filter(|key| own.binary_search(key).is_err())
+			.collect();
+		diff
+	}
+
+	/// Returns `true` if the entries stored under `key` in the [`Databoard`] itself and in `other` differ.
+	/// An entry deleted in the meantime counts as changed.
+	fn differs(&self, other: &Self, key: &str, registry: &CloneRegistry) -> bool {
+		self.database
+			.inspect(key, |own_id, own| {
+				other.database.inspect(key, |other_id, value| {
+					registry
+						.equals(own, value)
+						.map_or(own_id != other_id, |equal| !equal)
+				})
+			})
+			.and_then(|result| result)
+			.unwrap_or(true)
 	}
 
 	/// Returns a clone of the [`EntryPtr`] stored under `key`.
 	/// # Errors
 	/// - [`Error::Assignment`] if the remapping contains an assignment of a `str` value.
+	/// - [`Error::LockFree`]   if the entry is stored lock-free.
 	/// - [`Error::NoParent`]   if `key` is remapped to a parent without having a parent.
 	/// - [`Error::NotFound`]   if `key` is not contained.
 	pub fn entry(&self, key: &str) -> Result<EntryPtr> {
-		match check_top_level_key(key) {
+		let result = match check_top_level_key(key) {
 			Ok(stripped_key) => self.root().entry(stripped_key),
 			Err(original_key) => match check_local_key(original_key) {
-				Ok(local_key) => self.database.read().entry(local_key),
+				Ok(local_key) => self.database.entry(local_key),
 				Err(original_key) => {
 					let (parent_key, has_remapping) = self.remapping_info(original_key);
 					if has_remapping {
@@ -265,24 +540,48 @@ impl DataboardInner {
 						parent.entry(&parent_key)
 					} else {
 						// No remapping, use local database
-						self.database.read().entry(original_key)
+						self.database.entry(original_key)
 					}
 				}
 			},
-		}
+		};
+		result.map_err(|error| self.locate(key, error))
 	}
 
 	/// Returns a copy of the value of type `T` stored under `key`.
+	///
+	/// If the entry is not found, the [`Defaults`] of the [`Databoard`]s along the remapping chain are consulted,
+	/// starting with the [`Databoard`] responsible for the entry, each using the key it is accessed with.
+	/// # Errors
+	/// - [`Error::Assignment`] if the remapping contains an assignment of a `str` value.
+	/// - [`Error::NoParent`]   if `key` is remapped to a parent without having a parent.
+	/// - [`Error::NotFound`]   if `key` is neither contained nor has a default value.
+	/// - [`Error::WrongType`]  if the entry or the default value has not the expected type `T`.
+	pub fn get<T: Any + Clone + Send + Sync>(&self, key: &str) -> Result<T> {
+		let result = match self.get_stored(key) {
+			Err(Error::NotFound { .. }) if self.defaults.contains_key(key) => self.defaults.get(key),
+			result => result,
+		};
+		result.map_err(|error| self.locate(key, error))
+	}
+
+	/// Returns a read/write guard to the `T` of the `entry` stored under `key`.
+	/// The entry is locked for read & write while this reference is held.
+	/// Multiple changes during holding the reference are counted as a single change,
+	/// so `sequence_id()`will only increase by 1.
+	///
+	/// You need to drop the received [`EntryWriteGuard`] before using `delete`, `get`, `set` or `sequence_id`.
 	/// # Errors
 	/// - [`Error::Assignment`] if the remapping contains an assignment of a `str` value.
+	/// - [`Error::LockFree`]   if the entry is stored lock-free.
 	/// - [`Error::NoParent`]   if `key` is remapped to a parent without having a parent.
 	/// - [`Error::NotFound`]   if `key` is not contained.
 	/// - [`Error::WrongType`]  if the entry has not the expected type `T`.
-	pub fn get<T: Any + Clone + Send + Sync>(&self, key: &str) -> Result<T> {
-		match check_top_level_key(key) {
-			Ok(stripped_key) => self.root().get(stripped_key),
+	pub fn get_mut_ref<T: Any + Send + Sync>(&self, key: &str) -> Result<EntryWriteGuard<T>> {
+		let result = match check_top_level_key(key) {
+			Ok(stripped_key) => self.root().get_mut_ref(stripped_key),
 			Err(original_key) => match check_local_key(original_key) {
-				Ok(local_key) => self.database.read().read(local_key),
+				Ok(local_key) => self.database.get_mut_ref(local_key),
 				Err(original_key) => {
 					let (parent_key, has_remapping) = self.remapping_info(original_key);
 					if has_remapping {
@@ -301,39 +600,92 @@ impl DataboardInner {
 											remapped: board_pointer.into(),
 										})
 									},
-									|parent| parent.get(board_pointer),
+									|parent| parent.get_mut_ref(board_pointer),
 								)
 							},
 						)
 					} else if self.autoremap
 						&& let Some(parent) = &self.parent
 					{
-						parent.get(&parent_key)
+						parent.get_mut_ref(&parent_key)
 					} else {
 						// No remapping, use local database
-						self.database.read().read(original_key)
+						self.database.get_mut_ref(original_key)
 					}
 				}
 			},
-		}
+		};
+		result.map_err(|error| self.locate(key, error))
 	}
 
 	/// Returns a read/write guard to the `T` of the `entry` stored under `key`.
-	/// The entry is locked for read & write while this reference is held.
-	/// Multiple changes during holding the reference are counted as a single change,
-	/// so `sequence_id()`will only increase by 1.
+	/// If the entry is locked by someone else, further attempts are made as long as the `backoff` allows.
 	///
-	/// You need to drop the received [`EntryWriteGuard`] before using `delete`, `get`, `set` or `sequence_id`.
+	/// You need to drop the received [`EntryWriteGuard`] before using `delete`, `get...`, `set` or `sequence_id`.
 	/// # Errors
 	/// - [`Error::Assignment`] if the remapping contains an assignment of a `str` value.
+	/// - [`Error::IsLocked`]   if the entry is still locked by someone else when `backoff` gives up.
+	/// - [`Error::LockFree`]   if the entry is stored lock-free.
 	/// - [`Error::NoParent`]   if `key` is remapped to a parent without having a parent.
 	/// - [`Error::NotFound`]   if `key` is not contained.
 	/// - [`Error::WrongType`]  if the entry has not the expected type `T`.
-	pub fn get_mut_ref<T: Any + Send + Sync>(&self, key: &str) -> Result<EntryWriteGuard<T>> {
-		match check_top_level_key(key) {
-			Ok(stripped_key) => self.root().get_mut_ref(stripped_key),
+	pub fn get_mut_ref_timeout<T: Any + Send + Sync>(
+		&self,
+		key: &str,
+		mut backoff: impl Backoff,
+	) -> Result<EntryWriteGuard<T>> {
+		loop {
+			match self.try_get_mut_ref(key) {
+				Err(Error::IsLocked { key }) => {
+					if !backoff.backoff() {
+						return Err(Error::IsLocked { key });
+					}
+				}
+				result => return result,
+			}
+		}
+	}
+
+	/// Returns a copy of the value of type `T` stored under `key`, or `default` if `key` is not contained.
+	/// Other than `get(key).unwrap_or(default)` errors are still reported.
+	/// # Errors
+	/// - [`Error::Assignment`] if the remapping contains an assignment of a `str` value.
+	/// - [`Error::NoParent`]   if `key` is remapped to a parent without having a parent.
+	/// - [`Error::WrongType`]  if the entry or the default value has not the expected type `T`.
+	pub fn get_or<T: Any + Clone + Send + Sync>(&self, key: &str, default: T) -> Result<T> {
+		match self.get(key) {
+			Err(Error::NotFound { .. }) => Ok(default),
+			result => result,
+		}
+	}
+
+	/// Returns a copy of the value of type `T` stored under `key`, or `T::default()` if `key` is not contained.
+	/// # Errors
+	/// - [`Error::Assignment`] if the remapping contains an assignment of a `str` value.
+	/// - [`Error::NoParent`]   if `key` is remapped to a parent without having a parent.
+	/// - [`Error::WrongType`]  if the entry or the default value has not the expected type `T`.
+	pub fn get_or_default<T: Any + Clone + Default + Send + Sync>(&self, key: &str) -> Result<T> {
+		match self.get(key) {
+			Err(Error::NotFound { .. }) => Ok(T::default()),
+			result => result,
+		}
+	}
+
+	/// Returns a read guard to the `T` of the `entry` stored under `key`.
+	/// The entry is locked for write while this reference is held.
+	///
+	/// You need to drop the received [`EntryReadGuard`] before using `delete` or `set`.
+	/// # Errors
+	/// - [`Error::Assignment`] if the remapping contains an assignment of a `str` value.
+	/// - [`Error::LockFree`]   if the entry is stored lock-free.
+	/// - [`Error::NoParent`]   if `key` is remapped to a parent without having a parent.
+	/// - [`Error::NotFound`]   if `key` is not contained.
+	/// - [`Error::WrongType`]  if the entry has not the expected type `T`.
+	pub fn get_ref<T: Any + Send + Sync>(&self, key: &str) -> Result<EntryReadGuard<T>> {
+		let result = match check_top_level_key(key) {
+			Ok(stripped_key) => self.root().get_ref(stripped_key),
 			Err(original_key) => match check_local_key(original_key) {
-				Ok(local_key) => self.database.read().get_mut_ref(local_key),
+				Ok(local_key) => self.database.get_ref(local_key),
 				Err(original_key) => {
 					let (parent_key, has_remapping) = self.remapping_info(original_key);
 					if has_remapping {
@@ -352,37 +704,54 @@ impl DataboardInner {
 											remapped: board_pointer.into(),
 										})
 									},
-									|parent| parent.get_mut_ref(board_pointer),
+									|parent| parent.get_ref(board_pointer),
 								)
 							},
 						)
 					} else if self.autoremap
 						&& let Some(parent) = &self.parent
 					{
-						parent.get_mut_ref(&parent_key)
+						parent.get_ref(&parent_key)
 					} else {
 						// No remapping, use local database
-						self.database.read().get_mut_ref(original_key)
+						self.database.get_ref(original_key)
 					}
 				}
 			},
-		}
+		};
+		result.map_err(|error| self.locate(key, error))
 	}
 
 	/// Returns a read guard to the `T` of the `entry` stored under `key`.
-	/// The entry is locked for write while this reference is held.
+	/// If the entry is locked by someone else, further attempts are made as long as the `backoff` allows.
 	///
 	/// You need to drop the received [`EntryReadGuard`] before using `delete` or `set`.
 	/// # Errors
 	/// - [`Error::Assignment`] if the remapping contains an assignment of a `str` value.
+	/// - [`Error::IsLocked`]   if the entry is still locked by someone else when `backoff` gives up.
+	/// - [`Error::LockFree`]   if the entry is stored lock-free.
 	/// - [`Error::NoParent`]   if `key` is remapped to a parent without having a parent.
 	/// - [`Error::NotFound`]   if `key` is not contained.
 	/// - [`Error::WrongType`]  if the entry has not the expected type `T`.
-	pub fn get_ref<T: Any + Send + Sync>(&self, key: &str) -> Result<EntryReadGuard<T>> {
+	pub fn get_ref_timeout<T: Any + Send + Sync>(&self, key: &str, mut backoff: impl Backoff) -> Result<EntryReadGuard<T>> {
+		loop {
+			match self.try_get_ref(key) {
+				Err(Error::IsLocked { key }) => {
+					if !backoff.backoff() {
+						return Err(Error::IsLocked { key });
+					}
+				}
+				result => return result,
+			}
+		}
+	}
+
+	/// Returns a copy of the value of type `T` stored under `key` without consulting the [`Defaults`].
+	fn get_stored<T: Any + Clone + Send + Sync>(&self, key: &str) -> Result<T> {
 		match check_top_level_key(key) {
-			Ok(stripped_key) => self.root().get_ref(stripped_key),
+			Ok(stripped_key) => self.root().get(stripped_key),
 			Err(original_key) => match check_local_key(original_key) {
-				Ok(local_key) => self.database.read().get_ref(local_key),
+				Ok(local_key) => self.database.read(local_key),
 				Err(original_key) => {
 					let (parent_key, has_remapping) = self.remapping_info(original_key);
 					if has_remapping {
@@ -401,23 +770,190 @@ impl DataboardInner {
 											remapped: board_pointer.into(),
 										})
 									},
-									|parent| parent.get_ref(board_pointer),
+									|parent| parent.get(board_pointer),
 								)
 							},
 						)
 					} else if self.autoremap
 						&& let Some(parent) = &self.parent
 					{
-						parent.get_ref(&parent_key)
+						parent.get(&parent_key)
 					} else {
 						// No remapping, use local database
-						self.database.read().get_ref(original_key)
+						self.database.read(original_key)
 					}
 				}
 			},
 		}
 	}
 
+	/// Returns an upgradable read guard to the `T` of the `entry` stored under `key`.
+	/// The entry is locked for further readers and writers while this reference is held.
+	/// It can be upgraded to an [`EntryWriteGuard`] without releasing the lock in between.
+	///
+	/// You need to drop the received [`EntryUpgradableGuard`] before using `delete`, `get...`, `set` or `sequence_id`.
+	/// # Errors
+	/// - [`Error::Assignment`] if the remapping contains an assignment of a `str` value.
+	/// - [`Error::LockFree`]   if the entry is stored lock-free.
+	/// - [`Error::NoParent`]   if `key` is remapped to a parent without having a parent.
+	/// - [`Error::NotFound`]   if `key` is not contained.
+	/// - [`Error::WrongType`]  if the entry has not the expected type `T`.
+	pub fn get_upgradable_ref<T: Any + Send + Sync>(&self, key: &str) -> Result<EntryUpgradableGuard<T>> {
+		let (board, resolved) = self.resolve(key)?;
+		board
+			.database
+			.get_upgradable_ref(&resolved)
+			.map_err(|error| error.located(key, || board.level()))
+	}
+
+	/// Returns `true` if the [`Databoard`] itself does not contain any entry, otherwise `false`.
+	/// Entries of parents are not taken into account.
+	#[must_use]
+	pub fn is_empty(&self) -> bool {
+		self.database.is_empty()
+	}
+
+	/// Returns the cursor of the next change to be recorded in the journal of the [`Databoard`].
+	#[must_use]
+	pub fn journal_cursor(&self) -> u64 {
+		self.database.recorder().cursor()
+	}
+
+	/// Returns the sorted keys of the entries stored in the [`Databoard`] itself.
+	/// Entries of parents are not taken into account.
+	#[must_use]
+	pub fn keys(&self) -> Vec<ConstString> {
+		self.database.keys()
+	}
+
+	/// Returns the number of entries stored in the [`Databoard`] itself.
+	/// Entries of parents are not taken into account.
+	#[must_use]
+	pub fn len(&self) -> usize {
+		self.database.len()
+	}
+
+	/// Returns the level of the [`Databoard`] within the hierarchy, the root has level `0`.
+	#[must_use]
+	pub fn level(&self) -> usize {
+		self.parent
+			.as_ref()
+			.map_or(0, |parent| parent.level() + 1)
+	}
+
+	/// Adds `key` to an [`Error::NotFound`] or [`Error::WrongType`] as original key,
+	/// and the [`Databoard`] itself as the failing one, if the error did not occur in a parent.
+	fn locate(&self, key: &str, error: Error) -> Error {
+		error.located(key, || self.level())
+	}
+
+	/// Copies the entries stored in `other` itself into the [`Databoard`] itself
+	/// and returns the [`Diff`] found before merging.
+	///
+	/// Entries only contained in `other` are added, entries changed according to [`Databoard::diff`]
+	/// are handled as `policy` demands, entries only contained in the [`Databoard`] itself are kept.
+	/// Added and replaced entries take over the sequence id of `other`,
+	/// so they are no longer reported as changed by a following [`Databoard::diff`].
+	/// # Errors
+	/// - [`Error::Conflict`]     if `policy` is [`MergePolicy::Error`] and an entry changed, nothing is merged then.
+	/// - [`Error::InUse`]        if a replaced entry is still referenced elsewhere.
+	/// - [`Error::Unregistered`] if the type of a copied entry is not registered in `registry`.
+	pub fn merge_from(&self, other: &Self, registry: &CloneRegistry, policy: MergePolicy) -> Result<Diff> {
+		let diff = self.diff(other, registry);
+		if policy == MergePolicy::Error
+			&& let Some(key) = diff.changed.first()
+		{
+			return Err(Error::Conflict { key: key.clone() });
+		}
+		let changed = if policy == MergePolicy::Overwrite {
+			diff.changed.as_slice()
+		} else {
+			&[]
+		};
+		for key in diff.added.iter().chain(changed) {
+			match self
+				.database
+				.copy_from(&other.database, key, registry)
+			{
+				// deleted in the meantime
+				Ok(()) | Err(Error::NotFound { .. }) => {}
+				Err(error) => return Err(error),
+			}
+		}
+		Ok(diff)
+	}
+
+	/// Returns the modification counter of the [`Databoard`] itself.
+	///
+	/// It is increased with every creation, change or deletion of an entry of the [`Databoard`],
+	/// so comparing it with a previously read value detects whether anything changed.
+	/// Changes via an [`EntryPtr`] obtained by `entry` are not counted.
+	/// The counter wraps around to '1' after reaching [`SequenceId::MAX`].
+	#[must_use]
+	pub fn modifications(&self) -> SequenceId {
+		self.database.recorder().modifications()
+	}
+
+	/// Moves the entry stored under `key` to `new_key` in the `other` [`Databoard`].
+	/// Both keys are resolved using the remapping rules of their respective [`Databoard`].
+	///
+	/// The entry is transferred as a whole, so it keeps its `sequence_id`,
+	/// and already handed out references and guards stay valid.
+	/// Modifications via guards handed out before are still recorded in the journal under the old key.
+	/// # Errors
+	/// - [`Error::AlreadyExists`] if `new_key` already exists.
+	/// - [`Error::Assignment`]    if a remapping contains an assignment of a `str` value.
+	/// - [`Error::NoParent`]      if a key is remapped to a parent without having a parent.
+	/// - [`Error::NotFound`]      if `key` is not contained.
+	pub fn move_to(&self, key: &str, other: &Databoard, new_key: &str) -> Result<()> {
+		let (source, key) = self.resolve(key)?;
+		let (target, new_key) = other.resolve(new_key)?;
+		source
+			.database
+			.transfer(&key, &target.database, new_key)
+	}
+
+	/// Returns the parent [`Databoard`], if there is one.
+	pub(crate) const fn parent(&self) -> Option<&Databoard> {
+		self.parent.as_ref()
+	}
+
+	/// Returns the type erased value stored under `key` and deletes it from database.
+	/// In contrast to [`delete`](DataboardInner::delete) the type of the value needs not to be known.
+	/// Like `delete` it fails with [`Error::InUse`] if the entry is still referenced elsewhere.
+	/// # Errors
+	/// - [`Error::Assignment`] if the remapping contains an assignment of a `str` value.
+	/// - [`Error::InUse`]      if the entry is still referenced elsewhere.
+	/// - [`Error::NoParent`]   if `key` is remapped to a parent without having a parent.
+	/// - [`Error::NotFound`]   if `key` is not contained.
+	pub fn remove(&self, key: &str) -> Result<Box<dyn Any + Send + Sync>> {
+		let (board, resolved) = self.resolve(key)?;
+		board
+			.database
+			.remove(&resolved)
+			.map_err(|error| error.located(key, || board.level()))
+	}
+
+	/// Renames the entry stored under `old_key` to `new_key`.
+	/// Both keys are resolved using the remapping rules,
+	/// so the entry may end up in a different [`Databoard`] of the hierarchy.
+	///
+	/// The entry is transferred as a whole, so it keeps its `sequence_id`,
+	/// and already handed out references and guards stay valid.
+	/// Modifications via guards handed out before are still recorded in the journal under the old key.
+	/// # Errors
+	/// - [`Error::AlreadyExists`] if `new_key` already exists.
+	/// - [`Error::Assignment`]    if a remapping contains an assignment of a `str` value.
+	/// - [`Error::NoParent`]      if a key is remapped to a parent without having a parent.
+	/// - [`Error::NotFound`]      if `old_key` is not contained.
+	pub fn rename(&self, old_key: &str, new_key: &str) -> Result<()> {
+		let (source, old_key) = self.resolve(old_key)?;
+		let (target, new_key) = self.resolve(new_key)?;
+		source
+			.database
+			.transfer(&old_key, &target.database, new_key)
+	}
+
 	/// Returns a reference to the remappings, if there are any, otherwise `None`.
 	pub fn remappings(&self) -> Option<&Remappings> {
 		if self.remappings.is_empty() {
@@ -434,6 +970,48 @@ impl DataboardInner {
 			.map_or(self, |board| board.root())
 	}
 
+	/// Resolves `key` using the remapping rules.
+	/// Returns the [`Databoard`] which is responsible for the `key` together with the key valid there.
+	pub(crate) fn resolve(&self, key: &str) -> Result<(&Self, ConstString)> {
+		match check_top_level_key(key) {
+			Ok(stripped_key) => self.root().resolve(stripped_key),
+			Err(original_key) => match check_local_key(original_key) {
+				Ok(local_key) => Ok((self, local_key.into())),
+				Err(original_key) => {
+					let (parent_key, has_remapping) = self.remapping_info(original_key);
+					if has_remapping {
+						strip_board_pointer(&parent_key).map_or_else(
+							|| {
+								Err(Error::Assignment {
+									key: original_key.into(),
+									value: parent_key.clone(),
+								})
+							},
+							|board_pointer| {
+								self.parent.as_ref().map_or_else(
+									|| {
+										Err(Error::NoParent {
+											key: key.into(),
+											remapped: board_pointer.into(),
+										})
+									},
+									|parent| parent.resolve(board_pointer),
+								)
+							},
+						)
+					} else if self.autoremap
+						&& let Some(parent) = &self.parent
+					{
+						parent.resolve(&parent_key)
+					} else {
+						// No remapping, use local database
+						Ok((self, original_key.into()))
+					}
+				}
+			},
+		}
+	}
+
 	/// Read needed remapping information to parent.
 	fn remapping_info(&self, key: &str) -> (ConstString, bool) {
 		let (remapped_key, has_remapping) = self
@@ -446,16 +1024,16 @@ impl DataboardInner {
 
 	/// Returns the sequence id of an entry.
 	/// The sequence id starts with '1' and is increased at every change of an entry.
-	/// The sequence wraps around to '1' after reaching [`usize::MAX`] .
+	/// The sequence wraps around to '1' after reaching [`SequenceId::MAX`].
 	/// # Errors
 	/// - [`Error::Assignment`] if the remapping contains an assignment of a `str` value.
 	/// - [`Error::NoParent`]   if `key` is remapped to a parent without having a parent.
 	/// - [`Error::NotFound`]   if `key` is not contained.
-	pub fn sequence_id(&self, key: &str) -> Result<usize> {
-		match check_top_level_key(key) {
+	pub fn sequence_id(&self, key: &str) -> Result<SequenceId> {
+		let result = match check_top_level_key(key) {
 			Ok(stripped_key) => self.root().sequence_id(stripped_key),
 			Err(original_key) => match check_local_key(original_key) {
-				Ok(local_key) => self.database.read().sequence_id(local_key),
+				Ok(local_key) => self.database.sequence_id(local_key),
 				Err(original_key) => {
 					let (parent_key, has_remapping) = self.remapping_info(original_key);
 					if has_remapping {
@@ -484,11 +1062,12 @@ impl DataboardInner {
 						parent.sequence_id(&parent_key)
 					} else {
 						// No remapping, use local database
-						self.database.read().sequence_id(original_key)
+						self.database.sequence_id(original_key)
 					}
 				}
 			},
-		}
+		};
+		result.map_err(|error| self.locate(key, error))
 	}
 
 	/// Stores the value of type `T` under `key` and returns an eventually existing value of type `T`.
@@ -497,13 +1076,10 @@ impl DataboardInner {
 	/// - [`Error::NoParent`]   if `key` is remapped to a parent without having a parent.
 	/// - [`Error::WrongType`]  if `key` already exists with a different type.
 	pub fn set<T: Any + Send + Sync>(&self, key: &str, value: T) -> Result<Option<T>> {
-		match check_top_level_key(key) {
+		let result = match check_top_level_key(key) {
 			Ok(stripped_key) => self.root().set(stripped_key, value),
 			Err(original_key) => match check_local_key(original_key) {
-				Ok(local_key) => {
-					let old = self.database.read().update(local_key, value)?;
-					Ok(Some(old))
-				}
+				Ok(local_key) => self.database.set(local_key, value),
 				Err(original_key) => {
 					let (parent_key, has_remapping) = self.remapping_info(original_key);
 					if has_remapping {
@@ -532,19 +1108,44 @@ impl DataboardInner {
 						parent.set(&parent_key, value)
 					} else {
 						// No remapping, use local database
-						if self.contains_key(original_key) {
-							let old = self.database.read().update(original_key, value)?;
-							Ok(Some(old))
-						} else {
-							self.database
-								.write()
-								.create(original_key, value)?;
-							Ok(None)
-						}
+						self.database.set(original_key, value)
 					}
 				}
 			},
-		}
+		};
+		result.map_err(|error| self.locate(key, error))
+	}
+
+	/// Stores the value of type `T` under `key` lock-free and returns an eventually existing value of type `T`.
+	///
+	/// Lock-free entries are read and written by `get` and `set` without locking the entry,
+	/// but they can not be referenced, so `entry` and all `get...ref` methods fail with [`Error::LockFree`].
+	/// An already existing entry is updated the way it is stored.
+	/// # Errors
+	/// - [`Error::Assignment`] if the remapping contains an assignment of a `str` value.
+	/// - [`Error::NoParent`]   if `key` is remapped to a parent without having a parent.
+	/// - [`Error::WrongType`]  if `key` already exists with a different type.
+	pub fn set_atomic<T: AtomicValue>(&self, key: &str, value: T) -> Result<Option<T>> {
+		let (board, resolved) = self.resolve(key)?;
+		board
+			.database
+			.set_atomic(resolved, value)
+			.map_err(|error| error.located(key, || board.level()))
+	}
+
+	/// Sets the number of changes kept in the journal of the [`Databoard`], dropping the oldest changes if necessary.
+	/// The journal is disabled by default, a capacity of '0' disables it again.
+	pub fn set_journal_capacity(&self, capacity: usize) {
+		self.database.recorder().set_capacity(capacity);
+	}
+
+	/// Stores the value of type `T` under `key` in the [`Databoard`] itself and returns an eventually existing value of type `T`.
+	/// Remappings are not evaluated, a leading `_` of `key` is optional.
+	/// # Errors
+	/// - [`Error::WrongType`] if `key` already exists with a different type.
+	pub fn set_local<T: Any + Send + Sync>(&self, key: &str, value: T) -> Result<Option<T>> {
+		let key = check_local_key(key).unwrap_or(key);
+		self.database.set(key, value)
 	}
 
 	/// Returns a read/write guard to the `T` of the `entry` stored under `key`.
@@ -556,14 +1157,15 @@ impl DataboardInner {
 	/// # Errors
 	/// - [`Error::Assignment`] if the remapping contains an assignment of a `str` value.
 	/// - [`Error::IsLocked`]   if the entry is locked by someone else.
+	/// - [`Error::LockFree`]   if the entry is stored lock-free.
 	/// - [`Error::NoParent`]   if `key` is remapped to a parent without having a parent.
 	/// - [`Error::NotFound`]   if `key` is not contained.
 	/// - [`Error::WrongType`]  if the entry has not the expected type `T`.
 	pub fn try_get_mut_ref<T: Any + Send + Sync>(&self, key: &str) -> Result<EntryWriteGuard<T>> {
-		match check_top_level_key(key) {
+		let result = match check_top_level_key(key) {
 			Ok(stripped_key) => self.root().try_get_mut_ref(stripped_key),
 			Err(original_key) => match check_local_key(original_key) {
-				Ok(local_key) => self.database.read().try_get_mut_ref(local_key),
+				Ok(local_key) => self.database.try_get_mut_ref(local_key),
 				Err(original_key) => {
 					let (parent_key, has_remapping) = self.remapping_info(original_key);
 					if has_remapping {
@@ -592,11 +1194,12 @@ impl DataboardInner {
 						parent.try_get_mut_ref(&parent_key)
 					} else {
 						// No remapping, use local database
-						self.database.read().try_get_mut_ref(original_key)
+						self.database.try_get_mut_ref(original_key)
 					}
 				}
 			},
-		}
+		};
+		result.map_err(|error| self.locate(key, error))
 	}
 
 	/// Returns a read guard to the `T` of the `entry` stored under `key`.
@@ -606,14 +1209,15 @@ impl DataboardInner {
 	/// # Errors
 	/// - [`Error::Assignment`] if the remapping contains an assignment of a `str` value.
 	/// - [`Error::IsLocked`]   if the entry is locked by someone else.
+	/// - [`Error::LockFree`]   if the entry is stored lock-free.
 	/// - [`Error::NoParent`]   if `key` is remapped to a parent without having a parent.
 	/// - [`Error::NotFound`]   if `key` is not contained.
 	/// - [`Error::WrongType`]  if the entry has not the expected type `T`.
 	pub fn try_get_ref<T: Any + Send + Sync>(&self, key: &str) -> Result<EntryReadGuard<T>> {
-		match check_top_level_key(key) {
+		let result = match check_top_level_key(key) {
 			Ok(stripped_key) => self.root().try_get_ref(stripped_key),
 			Err(original_key) => match check_local_key(original_key) {
-				Ok(local_key) => self.database.read().try_get_ref(local_key),
+				Ok(local_key) => self.database.try_get_ref(local_key),
 				Err(original_key) => {
 					let (parent_key, has_remapping) = self.remapping_info(original_key);
 					if has_remapping {
@@ -639,14 +1243,45 @@ impl DataboardInner {
 					} else if self.autoremap
 						&& let Some(parent) = &self.parent
 					{
-						parent.get_ref(&parent_key)
+						parent.try_get_ref(&parent_key)
 					} else {
 						// No remapping, use local database
-						self.database.read().try_get_ref(original_key)
+						self.database.try_get_ref(original_key)
 					}
 				}
 			},
-		}
+		};
+		result.map_err(|error| self.locate(key, error))
+	}
+
+	/// Returns the name of the type stored under `key`, as given by [`core::any::type_name`] at creation.
+	/// # Errors
+	/// - [`Error::Assignment`] if the remapping contains an assignment of a `str` value.
+	/// - [`Error::NoParent`]   if `key` is remapped to a parent without having a parent.
+	/// - [`Error::NotFound`]   if `key` is not contained.
+	pub fn type_name_of(&self, key: &str) -> Result<&'static str> {
+		let (board, resolved) = self.resolve(key)?;
+		board
+			.database
+			.type_name(&resolved)
+			.map_err(|error| error.located(key, || board.level()))
+	}
+
+	/// Updates the value of type `T` stored under `key` and returns the old value.
+	///
+	/// In contrast to `set` a non existing entry is not created,
+	/// so this can be used to ensure that `key` did already exist.
+	/// # Errors
+	/// - [`Error::Assignment`] if the remapping contains an assignment of a `str` value.
+	/// - [`Error::NoParent`]   if `key` is remapped to a parent without having a parent.
+	/// - [`Error::NotFound`]   if `key` is not contained.
+	/// - [`Error::WrongType`]  if the entry has not the expected type `T`.
+	pub fn update<T: Any + Send + Sync>(&self, key: &str, value: T) -> Result<T> {
+		let (board, resolved) = self.resolve(key)?;
+		board
+			.database
+			.update(&resolved, value)
+			.map_err(|error| error.located(key, || board.level()))
 	}
 }
 
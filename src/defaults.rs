@@ -0,0 +1,95 @@
+// Copyright © 2025 Stephan Kunz
+//! Implements the [`Defaults`] table of a [`Databoard`](crate::Databoard).
+
+use crate::{cloner::CloneRegistry, database::Database, error::Result};
+use core::any::Any;
+
+#[cfg(doc)]
+use crate::Error;
+
+/// A table of default values, consulted by [`Databoard::get`](crate::databoard::DataboardInner::get)
+/// when an entry is not found.
+///
+/// The default values are stored under the key, with which the [`Databoard`](crate::Databoard) is accessed.
+pub struct Defaults(Database);
+
+impl core::fmt::Debug for Defaults {
+	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+		write!(f, "Defaults {{ {:?} }}", &self.0)
+	}
+}
+
+impl Default for Defaults {
+	fn default() -> Self {
+		Self(Database::new(1))
+	}
+}
+
+impl Defaults {
+	/// Creates an empty [`Defaults`] table.
+	#[must_use]
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Returns `true` if a default value for `key` is available, otherwise `false`.
+	#[must_use]
+	pub fn contains_key(&self, key: &str) -> bool {
+		self.0.contains_key(key)
+	}
+
+	/// Returns an independent copy of the table.
+	/// # Errors
+	/// - [`Error::Unregistered`] if the type of a default value is not registered in `registry`.
+	pub(crate) fn deep_clone(&self, registry: &CloneRegistry) -> Result<Self> {
+		self.0.deep_clone(registry).map(Self)
+	}
+
+	/// Returns the default value of type `T` for `key` and deletes it from the table.
+	/// # Errors
+	/// - [`Error::NotFound`]  if there is no default value for `key`.
+	/// - [`Error::WrongType`] if the default value has not the expected type `T`.
+	pub fn delete<T: Any + Send + Sync>(&self, key: &str) -> Result<T> {
+		self.0.delete(key)
+	}
+
+	/// Returns a copy of the default value of type `T` for `key`.
+	/// # Errors
+	/// - [`Error::NotFound`]  if there is no default value for `key`.
+	/// - [`Error::WrongType`] if the default value has not the expected type `T`.
+	pub fn get<T: Any + Clone + Send + Sync>(&self, key: &str) -> Result<T> {
+		self.0.read(key)
+	}
+
+	/// Returns `true` if the table contains no default values, otherwise `false`.
+	#[must_use]
+	pub fn is_empty(&self) -> bool {
+		self.0.is_empty()
+	}
+
+	/// Returns the number of default values in the table.
+	#[must_use]
+	pub fn len(&self) -> usize {
+		self.0.len()
+	}
+
+	/// Stores the default value of type `T` for `key` and returns an eventually existing default value.
+	/// # Errors
+	/// - [`Error::WrongType`] if the existing default value has not the type `T`.
+	pub fn set<T: Any + Send + Sync>(&self, key: &str, value: T) -> Result<Option<T>> {
+		self.0.set(key, value)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	// check, that the auto traits are available
+	const fn is_normal<T: Sized + Send + Sync>() {}
+
+	#[test]
+	const fn normal_types() {
+		is_normal::<Defaults>();
+	}
+}
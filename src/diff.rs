@@ -0,0 +1,54 @@
+// Copyright © 2025 Stephan Kunz
+//! Implements the [`Diff`] between two [`Databoard`](crate::Databoard)s and the [`MergePolicy`].
+
+use crate::ConstString;
+use alloc::vec::Vec;
+
+/// The differences between the entries of two [`Databoard`](crate::Databoard)s,
+/// as returned by [`Databoard::diff`](crate::databoard::DataboardInner::diff).
+///
+/// All lists are sorted by key.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Diff {
+	/// Keys only contained in the other [`Databoard`](crate::Databoard).
+	pub added: Vec<ConstString>,
+	/// Keys contained in both [`Databoard`](crate::Databoard)s with differing entries.
+	pub changed: Vec<ConstString>,
+	/// Keys only contained in the own [`Databoard`](crate::Databoard).
+	pub removed: Vec<ConstString>,
+}
+
+impl Diff {
+	/// Returns `true` if there are no differences, otherwise `false`.
+	#[must_use]
+	pub const fn is_empty(&self) -> bool {
+		self.added.is_empty() && self.changed.is_empty() && self.removed.is_empty()
+	}
+}
+
+/// Determines how [`Databoard::merge_from`](crate::databoard::DataboardInner::merge_from)
+/// handles entries, which are contained in both [`Databoard`](crate::Databoard)s with differing values.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum MergePolicy {
+	/// The own entry is replaced with a copy of the other entry.
+	#[default]
+	Overwrite,
+	/// The own entry is kept.
+	Keep,
+	/// Nothing is merged and an [`Error::Conflict`](crate::Error::Conflict) is returned.
+	Error,
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	// check, that the auto traits are available
+	const fn is_normal<T: Sized + Send + Sync>() {}
+
+	#[test]
+	const fn normal_types() {
+		is_normal::<Diff>();
+		is_normal::<MergePolicy>();
+	}
+}
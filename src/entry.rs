@@ -1,22 +1,33 @@
 // Copyright © 2025 Stephan Kunz
 //! Implementation of the entry for a [`Databoard`](crate::databoard::Databoard).
 
-use crate::{Error, error::Result};
+use crate::{
+	ConstString, Error,
+	error::Result,
+	journal::{Operation, Recorder},
+	sequence::{self, SequenceId},
+};
 use alloc::{boxed::Box, sync::Arc};
 use core::{
 	any::Any,
+	mem::ManuallyDrop,
 	ops::{Deref, DerefMut},
 };
-use spin::{RwLock, RwLockReadGuard, RwLockWriteGuard};
+use spin::{RwLock, RwLockReadGuard, RwLockUpgradableGuard, RwLockWriteGuard};
 
 /// Convenience type for the Arc around the [`EntryData`]
 pub type EntryPtr = Arc<RwLock<EntryData>>;
 
+/// The [`Recorder`] of the owning [`Database`](crate::database::Database) together with the key of the entry.
+type Tracker = (Arc<Recorder>, ConstString);
+
 // region:		--- EntryData
 /// The data stored in a [`Databoard`](crate::databoard::Databoard) entry.
 pub struct EntryData {
-	pub(crate) sequence_id: usize,
+	pub(crate) sequence_id: SequenceId,
 	pub(crate) data: Box<dyn Any + Send + Sync>,
+	/// Name of the stored type, recorded at creation.
+	pub(crate) type_name: &'static str,
 }
 
 impl Deref for EntryData {
@@ -39,6 +50,7 @@ impl EntryData {
 		Self {
 			data: Box::new(value),
 			sequence_id: 1,
+			type_name: core::any::type_name::<T>(),
 		}
 	}
 
@@ -48,9 +60,23 @@ impl EntryData {
 	}
 
 	/// Returns the current change iteration value.
-	pub const fn sequence_id(&self) -> usize {
+	pub const fn sequence_id(&self) -> SequenceId {
 		self.sequence_id
 	}
+
+	/// Returns the name of the stored type.
+	pub const fn type_name(&self) -> &'static str {
+		self.type_name
+	}
+
+	/// Replaces the stored data with `value` and returns the old value, if it is of type `T`.
+	/// The sequence id is increased.
+	pub fn swap<T: Any>(&mut self, mut value: T) -> Option<T> {
+		let t = self.data.downcast_mut::<T>()?;
+		core::mem::swap(t, &mut value);
+		self.sequence_id = sequence::next(self.sequence_id);
+		Some(value)
+	}
 }
 // endregion:	--- EntryData
 
@@ -86,6 +112,16 @@ impl<T: Any + Send + Sync> Drop for EntryReadGuard<T> {
 	}
 }
 
+// SAFETY: The guard owns an `Arc` to the entry, so the pointers stay valid wherever the guard is.
+// The `spin` lock is not bound to a thread, so it can be released from any thread.
+// Sending it is like sending a `&T`, which is fine as `T` is `Sync`.
+#[allow(unsafe_code)]
+unsafe impl<T: Any + Send + Sync> Send for EntryReadGuard<T> {}
+
+// SAFETY: A shared reference to the guard only provides a `&T`, which is fine as `T` is `Sync`.
+#[allow(unsafe_code)]
+unsafe impl<T: Any + Send + Sync> Sync for EntryReadGuard<T> {}
+
 impl<T: Any + Send + Sync> EntryReadGuard<T> {
 	/// Returns a read guard to a &T.
 	/// # Errors
@@ -94,13 +130,14 @@ impl<T: Any + Send + Sync> EntryReadGuard<T> {
 		// we know this pointer is valid since the guard owns the EntryPtr
 		let ptr_t = {
 			let guard = entry.read();
+			let stored = guard.type_name;
 			// leak returns &'rwlock mut EntryData but locks RwRLock forewer
 			let x = &RwLockReadGuard::leak(guard).data;
 			if let Some(t) = x.downcast_ref::<T>() {
 				let ptr_t: *const T = t;
 				ptr_t
 			} else {
-				return Err(Error::WrongType { key: key.into() });
+				return Err(Error::wrong_type::<T>(key, Some(stored)));
 			}
 		};
 
@@ -115,13 +152,14 @@ impl<T: Any + Send + Sync> EntryReadGuard<T> {
 		// we know this pointer is valid since the guard owns the EntryPtr
 		let ptr_t = {
 			if let Some(guard) = entry.try_read() {
+				let stored = guard.type_name;
 				// leak returns &'rlock EntryData but locks RwLock forewer
 				let x = &RwLockReadGuard::leak(guard).data;
 				if let Some(t) = x.downcast_ref::<T>() {
 					let ptr_t: *const T = t;
 					ptr_t
 				} else {
-					return Err(Error::WrongType { key: key.into() });
+					return Err(Error::wrong_type::<T>(key, Some(stored)));
 				}
 			} else {
 				return Err(Error::IsLocked { key: key.into() });
@@ -133,9 +171,159 @@ impl<T: Any + Send + Sync> EntryReadGuard<T> {
 			ptr_t,
 		})
 	}
+
+	/// Returns a read guard to a part `U` of the locked `T`, e.g. a field of a struct.
+	/// The lock on the entry is kept.
+	///
+	/// This is an associated function, to not interfere with methods of `T`.
+	#[must_use]
+	pub fn map<U: Any + Send + Sync>(this: Self, f: impl FnOnce(&T) -> &U) -> EntryReadGuard<U> {
+		let ptr_t: *const U = f(&*this);
+		EntryReadGuard {
+			entry: Self::into_entry(this),
+			ptr_t,
+		}
+	}
+
+	/// Returns a read guard to a part `U` of the locked `T`, if `f` returns one.
+	/// The lock on the entry is kept.
+	///
+	/// This is an associated function, to not interfere with methods of `T`.
+	/// # Errors
+	/// - if `f` returns `None`, the unchanged guard is returned.
+	pub fn try_map<U: Any + Send + Sync>(
+		this: Self,
+		f: impl FnOnce(&T) -> Option<&U>,
+	) -> core::result::Result<EntryReadGuard<U>, Self> {
+		let Some(u) = f(&*this) else {
+			return Err(this);
+		};
+		let ptr_t: *const U = u;
+		Ok(EntryReadGuard {
+			entry: Self::into_entry(this),
+			ptr_t,
+		})
+	}
+
+	/// Takes the [`EntryPtr`] out of the guard without releasing the lock.
+	fn into_entry(this: Self) -> EntryPtr {
+		let this = ManuallyDrop::new(this);
+		#[allow(unsafe_code)]
+		// SAFETY: `this` is never dropped, so the `entry` is moved out exactly once and the lock stays held.
+		unsafe {
+			core::ptr::read(&raw const this.entry)
+		}
+	}
 }
 // endregion:	--- EntryReadGuard
 
+// region:		--- EntryUpgradableGuard
+/// Upgradable read-locked entry guard.
+/// Until this value is dropped or upgraded, an upgradable read lock is held on the entry.
+/// While it is held, no further readers or writers are admitted.
+///
+/// Implements [`Deref`], providing read access to the locked `T`.
+pub struct EntryUpgradableGuard<T: Any + Send + Sync> {
+	/// The held lock, borrowing from `entry`, so it must be declared and dropped before `entry`.
+	guard: RwLockUpgradableGuard<'static, EntryData>,
+	/// `Arc` to an `Entry`
+	entry: EntryPtr,
+	/// Pointer to content of the `Entry` above
+	ptr_t: *const T,
+	/// [`Recorder`] of the owning [`Database`](crate::database::Database) and key of the entry, handed over on upgrade
+	tracker: Option<Tracker>,
+}
+
+impl<T: Any + Send + Sync> Deref for EntryUpgradableGuard<T> {
+	type Target = T;
+
+	#[allow(unsafe_code)]
+	fn deref(&self) -> &Self::Target {
+		// SAFETY: Self referencing to locked content of the `Arc` `Entry`, valid until self is dropped
+		unsafe { &*self.ptr_t }
+	}
+}
+
+// SAFETY: The guard owns an `Arc` to the entry, so the pointers stay valid wherever the guard is.
+// The `spin` lock is not bound to a thread, so it can be released from any thread.
+// Sending it is like sending a `&T`, which is fine as `T` is `Sync`.
+#[allow(unsafe_code)]
+unsafe impl<T: Any + Send + Sync> Send for EntryUpgradableGuard<T> {}
+
+// SAFETY: A shared reference to the guard only provides a `&T`, which is fine as `T` is `Sync`.
+#[allow(unsafe_code)]
+unsafe impl<T: Any + Send + Sync> Sync for EntryUpgradableGuard<T> {}
+
+impl<T: Any + Send + Sync> EntryUpgradableGuard<T> {
+	/// Returns an upgradable read guard to a &T.
+	/// # Errors
+	/// - [`Error::WrongType`] if the entry has not the expected type `T`.
+	pub fn new(key: &str, entry: EntryPtr) -> Result<Self> {
+		#[allow(unsafe_code)]
+		// SAFETY: The guard borrows the `RwLock` within the `Arc` allocation, which never moves.
+		// It is kept alive by `entry`, which is stored together with the guard and dropped after it.
+		let guard = unsafe {
+			core::mem::transmute::<RwLockUpgradableGuard<'_, EntryData>, RwLockUpgradableGuard<'static, EntryData>>(
+				entry.upgradeable_read(),
+			)
+		};
+		let ptr_t = if let Some(t) = guard.data.downcast_ref::<T>() {
+			let ptr_t: *const T = t;
+			ptr_t
+		} else {
+			return Err(Error::wrong_type::<T>(key, Some(guard.type_name)));
+		};
+
+		Ok(Self {
+			guard,
+			entry,
+			ptr_t,
+			tracker: None,
+		})
+	}
+
+	/// Registers the `recorder`, which records modifications of the entry stored under `key`.
+	pub(crate) fn tracked(mut self, recorder: &Arc<Recorder>, key: &str) -> Self {
+		self.tracker = Some((recorder.clone(), key.into()));
+		self
+	}
+
+	/// Upgrades the guard into an [`EntryWriteGuard`] without releasing the lock in between.
+	/// Waits until all readers have released their locks.
+	///
+	/// The `sequence_id` of the entry is only increased, if the value is modified via the [`EntryWriteGuard`].
+	/// Upgrading while holding an [`EntryReadGuard`] to the same entry will block forever.
+	#[must_use]
+	pub fn upgrade(self) -> EntryWriteGuard<T> {
+		let Self {
+			guard, entry, tracker, ..
+		} = self;
+		EntryWriteGuard::from_locked(entry, guard.upgrade(), tracker)
+	}
+
+	/// Tries to upgrade the guard into an [`EntryWriteGuard`] without releasing the lock in between.
+	/// # Errors
+	/// - if there are still readers, the unchanged guard is returned.
+	pub fn try_upgrade(self) -> core::result::Result<EntryWriteGuard<T>, Self> {
+		let Self {
+			guard,
+			entry,
+			ptr_t,
+			tracker,
+		} = self;
+		match guard.try_upgrade() {
+			Ok(write_guard) => Ok(EntryWriteGuard::from_locked(entry, write_guard, tracker)),
+			Err(guard) => Err(Self {
+				guard,
+				entry,
+				ptr_t,
+				tracker,
+			}),
+		}
+	}
+}
+// endregion:	--- EntryUpgradableGuard
+
 // region:		--- EntryWriteGuard
 /// Write-Locked entry guard.
 /// Until this value is dropped, a write lock is held on the entry.
@@ -144,8 +332,10 @@ impl<T: Any + Send + Sync> EntryReadGuard<T> {
 pub struct EntryWriteGuard<T: Any + Send + Sync> {
 	entry: EntryPtr,
 	ptr_t: *mut T,
-	ptr_seq_id: *mut usize,
+	ptr_seq_id: *mut SequenceId,
 	modified: bool,
+	/// [`Recorder`] of the owning [`Database`](crate::database::Database) and key of the entry
+	tracker: Option<Tracker>,
 }
 
 impl<T: Any + Send + Sync> Deref for EntryWriteGuard<T> {
@@ -173,14 +363,42 @@ impl<T: Any + Send + Sync> Drop for EntryWriteGuard<T> {
 		// SAFETY: manually removing lock because entry is permanently locked in new()
 		unsafe {
 			if self.modified {
-				*self.ptr_seq_id += 1;
+				*self.ptr_seq_id = sequence::next(*self.ptr_seq_id);
+				if let Some((recorder, key)) = &self.tracker {
+					recorder.record(key, Operation::Modify, *self.ptr_seq_id);
+				}
 			}
 			self.entry.force_write_unlock();
 		}
 	}
 }
 
+// SAFETY: The guard owns an `Arc` to the entry, so the pointers stay valid wherever the guard is.
+// The `spin` lock is not bound to a thread, so it can be released from any thread.
+// Sending it is like sending a `&mut T`, which is fine as `T` is `Send`.
+#[allow(unsafe_code)]
+unsafe impl<T: Any + Send + Sync> Send for EntryWriteGuard<T> {}
+
+// SAFETY: A shared reference to the guard only provides a `&T`, which is fine as `T` is `Sync`.
+#[allow(unsafe_code)]
+unsafe impl<T: Any + Send + Sync> Sync for EntryWriteGuard<T> {}
+
 impl<T: Any + Send + Sync> EntryWriteGuard<T> {
+	/// Creates the guard from an already write locked `entry`, whose type has been checked to be `T`.
+	fn from_locked(entry: EntryPtr, guard: RwLockWriteGuard<'static, EntryData>, tracker: Option<Tracker>) -> Self {
+		// leak returns &'rwlock mut EntryData but locks RwLock forewer
+		let data = RwLockWriteGuard::leak(guard);
+		let ptr_seq_id: *mut SequenceId = &raw mut data.sequence_id;
+		let ptr_any: *mut (dyn Any + Send + Sync) = &raw mut *data.data;
+		Self {
+			entry,
+			ptr_t: ptr_any.cast::<T>(),
+			ptr_seq_id,
+			modified: false,
+			tracker,
+		}
+	}
+
 	/// Returns a write guard to a &mut T.
 	/// # Errors
 	/// - [`Error::WrongType`] if the entry has not the expected type `T`.
@@ -188,14 +406,15 @@ impl<T: Any + Send + Sync> EntryWriteGuard<T> {
 		// we know this pointer is valid since the guard owns the EntryPtr
 		let (ptr_t, ptr_seq_id) = {
 			let mut guard = entry.write();
-			let ptr_seq_id: *mut usize = &raw mut guard.sequence_id;
+			let ptr_seq_id: *mut SequenceId = &raw mut guard.sequence_id;
+			let stored = guard.type_name;
 			// leak returns &'rwlock mut EntryData but locks RwLock forewer
 			let x = &mut RwLockWriteGuard::leak(guard).data;
 			if let Some(t) = x.downcast_mut::<T>() {
 				let ptr_t: *mut T = t;
 				(ptr_t, ptr_seq_id)
 			} else {
-				return Err(Error::WrongType { key: key.into() });
+				return Err(Error::wrong_type::<T>(key, Some(stored)));
 			}
 		};
 
@@ -204,6 +423,7 @@ impl<T: Any + Send + Sync> EntryWriteGuard<T> {
 			ptr_t,
 			ptr_seq_id,
 			modified: false,
+			tracker: None,
 		})
 	}
 
@@ -215,14 +435,15 @@ impl<T: Any + Send + Sync> EntryWriteGuard<T> {
 		// we know this pointer is valid since the guard owns the EntryPtr
 		let (ptr_t, ptr_seq_id) = {
 			if let Some(mut guard) = entry.try_write() {
-				let ptr_seq_id: *mut usize = &raw mut guard.sequence_id;
+				let ptr_seq_id: *mut SequenceId = &raw mut guard.sequence_id;
+				let stored = guard.type_name;
 				// leak returns &'rwlock mut EntryData but locks RwLock forewer
 				let x = &mut RwLockWriteGuard::leak(guard).data;
 				if let Some(t) = x.downcast_mut::<T>() {
 					let ptr_t: *mut T = t;
 					(ptr_t, ptr_seq_id)
 				} else {
-					return Err(Error::WrongType { key: key.into() });
+					return Err(Error::wrong_type::<T>(key, Some(stored)));
 				}
 			} else {
 				return Err(Error::IsLocked { key: key.into() });
@@ -234,8 +455,84 @@ impl<T: Any + Send + Sync> EntryWriteGuard<T> {
 			ptr_t,
 			ptr_seq_id,
 			modified: false,
+			tracker: None,
+		})
+	}
+
+	/// Registers the `recorder`, which records modifications of the entry stored under `key`.
+	pub(crate) fn tracked(mut self, recorder: &Arc<Recorder>, key: &str) -> Self {
+		self.tracker = Some((recorder.clone(), key.into()));
+		self
+	}
+
+	/// Returns a write guard to a part `U` of the locked `T`, e.g. a field of a struct.
+	/// The lock on the entry is kept.
+	///
+	/// As `f` gets mutable access to the whole `T`, mapping counts as modification
+	/// and increases the `sequence_id` when the returned guard is dropped.
+	///
+	/// This is an associated function, to not interfere with methods of `T`.
+	#[must_use]
+	pub fn map<U: Any + Send + Sync>(mut this: Self, f: impl FnOnce(&mut T) -> &mut U) -> EntryWriteGuard<U> {
+		this.modified = true;
+		#[allow(unsafe_code)]
+		// SAFETY: Self referencing to locked content of the `Arc` `Entry`, not using `deref_mut()`
+		// as the returned reference must not borrow `this`
+		let ptr_t: *mut U = f(unsafe { &mut *this.ptr_t });
+		let (entry, ptr_seq_id, modified, tracker) = Self::into_parts(this);
+		EntryWriteGuard {
+			entry,
+			ptr_t,
+			ptr_seq_id,
+			modified,
+			tracker,
+		}
+	}
+
+	/// Returns a write guard to a part `U` of the locked `T`, if `f` returns one.
+	/// The lock on the entry is kept.
+	///
+	/// As `f` gets mutable access to the whole `T`, mapping counts as modification,
+	/// even if `f` returns `None`.
+	///
+	/// This is an associated function, to not interfere with methods of `T`.
+	/// # Errors
+	/// - if `f` returns `None`, the guard is returned.
+	pub fn try_map<U: Any + Send + Sync>(
+		mut this: Self,
+		f: impl FnOnce(&mut T) -> Option<&mut U>,
+	) -> core::result::Result<EntryWriteGuard<U>, Self> {
+		this.modified = true;
+		#[allow(unsafe_code)]
+		// SAFETY: Self referencing to locked content of the `Arc` `Entry`, not using `deref_mut()`
+		// as the returned reference must not borrow `this`
+		let Some(u) = f(unsafe { &mut *this.ptr_t }) else {
+			return Err(this);
+		};
+		let ptr_t: *mut U = u;
+		let (entry, ptr_seq_id, modified, tracker) = Self::into_parts(this);
+		Ok(EntryWriteGuard {
+			entry,
+			ptr_t,
+			ptr_seq_id,
+			modified,
+			tracker,
 		})
 	}
+
+	/// Takes the parts independent of `T` out of the guard without releasing the lock.
+	fn into_parts(this: Self) -> (EntryPtr, *mut SequenceId, bool, Option<Tracker>) {
+		let this = ManuallyDrop::new(this);
+		#[allow(unsafe_code)]
+		// SAFETY: `this` is never dropped, so `entry` and `tracker` are moved out exactly once and the lock stays held.
+		let (entry, tracker) = unsafe {
+			(
+				core::ptr::read(&raw const this.entry),
+				core::ptr::read(&raw const this.tracker),
+			)
+		};
+		(entry, this.ptr_seq_id, this.modified, tracker)
+	}
 }
 // endregion:	--- EntryWriteGuard
 
@@ -256,7 +553,33 @@ mod tests {
 		is_normal::<Dummy>();
 		is_normal::<EntryData>();
 		is_normal::<EntryPtr>();
-		// is_normal::<EntryReadGuard<Dummy>>();
-		// is_normal::<EntryWriteGuard<Dummy>>();
+		is_normal::<EntryReadGuard<Dummy>>();
+		is_normal::<EntryUpgradableGuard<Dummy>>();
+		is_normal::<EntryWriteGuard<Dummy>>();
+	}
+
+	#[test]
+	fn sequence_id_wrap_around() {
+		let mut data = EntryData::new(1_i32);
+		data.sequence_id = SequenceId::MAX;
+		let entry: EntryPtr = Arc::new(RwLock::new(data));
+		let recorder = Arc::new(Recorder::default());
+
+		if let Ok(guard) = EntryWriteGuard::<i32>::try_new("test", &entry) {
+			let mut guard = guard.tracked(&recorder, "test");
+			*guard = 2;
+		}
+		assert_eq!(entry.read().sequence_id(), 1);
+		assert_eq!(recorder.modifications(), 1);
+
+		// not modified
+		if let Ok(guard) = EntryWriteGuard::<i32>::try_new("test", &entry) {
+			drop(guard.tracked(&recorder, "test"));
+		}
+		assert_eq!(entry.read().sequence_id(), 1);
+		assert_eq!(recorder.modifications(), 1);
+
+		assert_eq!(entry.write().swap(3_i32), Some(2));
+		assert_eq!(entry.read().sequence_id(), 2);
 	}
 }
@@ -2,10 +2,49 @@
 //! The [`Databoard`](crate::Databoard) error handling.
 
 use crate::ConstString;
+use alloc::boxed::Box;
 
 /// Shortcut for [`Databoard`](crate::Databoard)'s Result<T, E> type
 pub type Result<T> = core::result::Result<T, Error>;
 
+/// Additional information on an [`Error::NotFound`] or [`Error::WrongType`].
+///
+/// The parts are filled in where they are known, so each of them is optional.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Context {
+	/// The key as passed to the [`Databoard`](crate::Databoard).
+	pub original: Option<ConstString>,
+	/// The key after applying the remappings.
+	pub remapped: Option<ConstString>,
+	/// Level of the [`Databoard`](crate::Databoard) where the lookup failed, the root has level `0`.
+	pub level: Option<usize>,
+	/// Name of the expected type.
+	pub expected: Option<&'static str>,
+	/// Name of the stored type.
+	pub stored: Option<&'static str>,
+}
+
+impl core::fmt::Display for Context {
+	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+		if let Some(original) = &self.original {
+			write!(f, ", accessed as {original}")?;
+		}
+		if let Some(remapped) = &self.remapped {
+			write!(f, ", remapped to {remapped}")?;
+		}
+		if let Some(level) = self.level {
+			write!(f, " at level {level}")?;
+		}
+		if let Some(expected) = self.expected {
+			write!(f, ", expected type {expected}")?;
+		}
+		if let Some(stored) = self.stored {
+			write!(f, ", stored type {stored}")?;
+		}
+		Ok(())
+	}
+}
+
 /// Things that may go wrong using the [`Databoard`](crate::Databoard).
 #[non_exhaustive]
 pub enum Error {
@@ -28,11 +67,26 @@ pub enum Error {
 		/// Value to be asssigned.
 		value: ConstString,
 	},
+	/// Entry with `key` differs between two [`Databoard`](crate::Databoard)s to be merged.
+	Conflict {
+		/// Key of the conflicting entry.
+		key: ConstString,
+	},
+	/// Entry with `key` is still referenced elsewhere.
+	InUse {
+		/// Key of the wanted entry.
+		key: ConstString,
+	},
 	/// Entry with `key` is locked.
 	IsLocked {
 		/// Key of the wanted entry.
 		key: ConstString,
 	},
+	/// Entry with `key` is stored lock-free and can not be referenced.
+	LockFree {
+		/// Key of the wanted entry.
+		key: ConstString,
+	},
 	/// Defined a remapping without giving a parent.
 	NoParent {
 		/// Wanted key.
@@ -41,24 +95,111 @@ pub enum Error {
 		remapped: ConstString,
 	},
 	/// Entry with `key` not stored.
+	#[non_exhaustive]
 	NotFound {
 		/// Key of the wanted entry.
 		key: ConstString,
+		/// Optional information on where the lookup failed.
+		context: Option<Box<Context>>,
+	},
+	/// Constant assignment `value` of `key` can not be parsed into the wanted type.
+	Parse {
+		/// Key of the remapping.
+		key: ConstString,
+		/// The assigned value.
+		value: ConstString,
+	},
+	/// Access to a port failed.
+	Port {
+		/// Name of the port.
+		port: ConstString,
+		/// The remapped target of the port.
+		remapped: ConstString,
+		/// Level of the [`Databoard`](crate::Databoard) responsible for the target.
+		level: usize,
+		/// The reason of the failure.
+		source: Box<Error>,
+	},
+	/// Communication with a remote [`Databoard`](crate::Databoard) failed.
+	Remote {
+		/// Description of the failure.
+		message: ConstString,
+	},
+	/// Entry with `key` has a type, which is not registered in a [`Registry`](crate::Registry)
+	/// or [`CloneRegistry`](crate::CloneRegistry).
+	Unregistered {
+		/// Key of the wanted entry.
+		key: ConstString,
 	},
 	/// Entry with `key` is stored with a different type.
+	#[non_exhaustive]
 	WrongType {
 		/// Key of the wanted entry.
 		key: ConstString,
+		/// Optional information on where the lookup failed and on the involved types.
+		context: Option<Box<Context>>,
 	},
 	/// Something impossible happened.
 	Unreachable(ConstString, u32),
 }
 
-/// Currently the default implementation is sufficient.
+impl Error {
+	/// Creates an [`Error::NotFound`] without context.
+	pub(crate) fn not_found(key: impl Into<ConstString>) -> Self {
+		Self::NotFound {
+			key: key.into(),
+			context: None,
+		}
+	}
+
+	/// Creates an [`Error::WrongType`] expecting the type `T`, where the `stored` type is known.
+	pub(crate) fn wrong_type<T: ?Sized>(key: impl Into<ConstString>, stored: Option<&'static str>) -> Self {
+		Self::WrongType {
+			key: key.into(),
+			context: Some(Box::new(Context {
+				expected: Some(core::any::type_name::<T>()),
+				stored,
+				..Context::default()
+			})),
+		}
+	}
+
+	/// Returns the [`Context`] of an [`Error::NotFound`] or [`Error::WrongType`], if available.
+	#[must_use]
+	pub fn context(&self) -> Option<&Context> {
+		match self {
+			Self::NotFound { context, .. } | Self::WrongType { context, .. } => context.as_deref(),
+			_ => None,
+		}
+	}
+
+	/// Adds the `original` key to an [`Error::NotFound`] or [`Error::WrongType`], other errors are returned unchanged.
+	///
+	/// If the [`Databoard`](crate::Databoard) where the lookup failed is not yet known,
+	/// the key of the error is taken as remapped key and `level` is called to determine its level.
+	/// So each [`Databoard`](crate::Databoard) along a remapping chain can call this in constant time,
+	/// the outermost one determines the original key.
+	#[must_use]
+	pub(crate) fn located(mut self, original: &str, level: impl FnOnce() -> usize) -> Self {
+		if let Self::NotFound { key, context } | Self::WrongType { key, context } = &mut self {
+			let context = context.get_or_insert_with(Box::default);
+			if context.level.is_none() {
+				context.remapped = Some(key.clone());
+				context.level = Some(level());
+			}
+			context.original = Some(original.into());
+		}
+		self
+	}
+}
+
 impl core::error::Error for Error {
-	// fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
-	// 	None
-	// }
+	fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+		match self {
+			Self::Port { source, .. } => Some(source.as_ref()),
+			_ => None,
+		}
+	}
 
 	// fn cause(&self) -> Option<&dyn core::error::Error> {
 	// 	self.source()
@@ -75,10 +216,31 @@ impl core::fmt::Debug for Error {
 				write!(f, "AlreadyRemapped(key: {key}, remapped: {remapped})")
 			}
 			Self::Assignment { key, value } => write!(f, "Assignment(key: {key}, value: {value})"),
+			Self::Conflict { key } => write!(f, "Conflict(key: {key})"),
+			Self::InUse { key } => write!(f, "InUse(key: {key})"),
 			Self::IsLocked { key } => write!(f, "Locked(key: {key}"),
+			Self::LockFree { key } => write!(f, "LockFree(key: {key})"),
 			Self::NoParent { key, remapped } => write!(f, "NoParent(key: {key}, remapped: {remapped})"),
-			Self::NotFound { key } => write!(f, "NotFound(key: {key})"),
-			Self::WrongType { key } => write!(f, "WrongType(key: {key})"),
+			Self::NotFound { key, context } => match context {
+				Some(context) => write!(f, "NotFound(key: {key}, context: {context:?})"),
+				None => write!(f, "NotFound(key: {key})"),
+			},
+			Self::Parse { key, value } => write!(f, "Parse(key: {key}, value: {value})"),
+			Self::Port {
+				port,
+				remapped,
+				level,
+				source,
+			} => write!(
+				f,
+				"Port(port: {port}, remapped: {remapped}, level: {level}, source: {source:?})"
+			),
+			Self::Remote { message } => write!(f, "Remote(message: {message})"),
+			Self::Unregistered { key } => write!(f, "Unregistered(key: {key})"),
+			Self::WrongType { key, context } => match context {
+				Some(context) => write!(f, "WrongType(key: {key}, context: {context:?})"),
+				None => write!(f, "WrongType(key: {key})"),
+			},
 			Self::Unreachable(file, line) => write!(f, "Unreachable(file: {file}, line: {line})"),
 		}
 	}
@@ -92,10 +254,34 @@ impl core::fmt::Display for Error {
 				write!(f, "key {key} is already remapped as {remapped}")
 			}
 			Self::Assignment { key, value } => write!(f, "remapping of {key} contains an assignment of {value}"),
+			Self::Conflict { key } => write!(f, "the entry {key} differs in the merged databoards"),
+			Self::InUse { key } => write!(f, "the entry {key} is still in use"),
 			Self::IsLocked { key } => write!(f, "the entry {key} is locked"),
+			Self::LockFree { key } => write!(f, "the entry {key} is stored lock-free and can not be referenced"),
 			Self::NoParent { key, remapped } => write!(f, "remapping of {key} to {remapped} without a parent board"),
-			Self::NotFound { key } => write!(f, "an entry for the key {key} is not existing"),
-			Self::WrongType { key } => write!(f, "the entry for the key {key} is stored with a different type"),
+			Self::NotFound { key, context } => {
+				write!(f, "an entry for the key {key} is not existing")?;
+				context
+					.as_ref()
+					.map_or(Ok(()), |context| write!(f, "{context}"))
+			}
+			Self::Parse { key, value } => write!(f, "assignment {value} of {key} can not be parsed"),
+			Self::Port {
+				port,
+				remapped,
+				level,
+				source,
+			} => write!(f, "port {port} remapped to {remapped} at level {level}: {source}"),
+			Self::Remote { message } => write!(f, "communication with a remote databoard failed: {message}"),
+			Self::Unregistered { key } => {
+				write!(f, "the entry for the key {key} has a type which is not registered")
+			}
+			Self::WrongType { key, context } => {
+				write!(f, "the entry for the key {key} is stored with a different type")?;
+				context
+					.as_ref()
+					.map_or(Ok(()), |context| write!(f, "{context}"))
+			}
 			Self::Unreachable(file, line) => write!(f, "an unexpected error occured in {file} at line {line}"),
 		}
 	}
@@ -110,6 +296,7 @@ mod tests {
 
 	#[test]
 	const fn normal_types() {
+		is_normal::<Context>();
 		is_normal::<Error>();
 	}
 }
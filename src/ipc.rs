@@ -0,0 +1,183 @@
+// Copyright © 2025 Stephan Kunz
+//! Sharing a [`Databoard`] between processes via a Unix domain socket.
+
+extern crate std;
+
+use crate::{
+	Databoard, Error,
+	codec::Registry,
+	error::Result,
+	protocol::{RemoteDataboard, serve},
+};
+use alloc::{sync::Arc, vec::Vec};
+use core::sync::atomic::{AtomicBool, Ordering};
+use std::{
+	net::Shutdown,
+	os::unix::net::{UnixListener, UnixStream},
+	path::{Path, PathBuf},
+	thread::JoinHandle,
+};
+
+/// Converts an I/O error into an [`Error::Remote`].
+#[allow(clippy::needless_pass_by_value)]
+fn io_error(error: std::io::Error) -> Error {
+	Error::Remote {
+		message: alloc::format!("{error}").into(),
+	}
+}
+
+/// Returns the [`Error::Remote`] for a panicked thread.
+fn panicked() -> Error {
+	Error::Remote {
+		message: "server thread panicked".into(),
+	}
+}
+
+/// A served connection.
+struct Connection {
+	/// Used to close the connection on shutdown.
+	stream: UnixStream,
+	/// The thread serving the connection.
+	thread: JoinHandle<()>,
+}
+
+/// Serves a [`Databoard`] to other processes via a Unix domain socket.
+///
+/// Each connected [`RemoteDataboard`] is served by its own thread.
+/// The socket file is removed when the [`DataboardServer`] is dropped.
+pub struct DataboardServer {
+	listener: UnixListener,
+	path: PathBuf,
+	board: Databoard,
+	registry: Arc<Registry>,
+	stopped: Arc<AtomicBool>,
+}
+
+impl core::fmt::Debug for DataboardServer {
+	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+		write!(f, "DataboardServer {{ {:?}, {:?} }}", &self.listener, &self.registry)
+	}
+}
+
+impl Drop for DataboardServer {
+	fn drop(&mut self) {
+		// the socket file may already have been removed by someone else
+		let _ = std::fs::remove_file(&self.path);
+	}
+}
+
+impl DataboardServer {
+	/// Creates a [`DataboardServer`] for `board` listening at `path`.
+	/// # Errors
+	/// - [`Error::Remote`] if the socket can not be created.
+	pub fn bind(path: impl AsRef<Path>, board: Databoard, registry: Arc<Registry>) -> Result<Self> {
+		let path = path.as_ref().to_path_buf();
+		let listener = UnixListener::bind(&path).map_err(io_error)?;
+		Ok(Self {
+			listener,
+			path,
+			board,
+			registry,
+			stopped: Arc::default(),
+		})
+	}
+
+	/// Accepts connections and serves them, until accepting a connection fails
+	/// or the server is stopped with [`ServerHandle::shutdown`].
+	///
+	/// On return all connections are closed and the threads serving them are joined.
+	/// A failing connection only affects its own client, it does not stop the server.
+	/// # Errors
+	/// - [`Error::Remote`] if accepting a connection fails.
+	pub fn run(&self) -> Result<()> {
+		let mut connections: Vec<Connection> = Vec::new();
+		let result = loop {
+			let accepted = self.listener.accept();
+			if self.stopped.load(Ordering::SeqCst) {
+				break Ok(());
+			}
+			let connection = match accepted.and_then(|(stream, _)| stream.try_clone().map(|clone| (stream, clone))) {
+				Ok((stream, clone)) => {
+					let board = self.board.clone();
+					let registry = self.registry.clone();
+					Connection {
+						stream: clone,
+						thread: std::thread::spawn(move || {
+							// a broken connection is the problem of its client only
+							let _ = serve(&board, &registry, stream);
+						}),
+					}
+				}
+				Err(error) => break Err(io_error(error)),
+			};
+			// forget the already closed connections
+			connections.retain(|connection| !connection.thread.is_finished());
+			connections.push(connection);
+		};
+		for connection in &connections {
+			// an already closed connection can not be shut down
+			let _ = connection.stream.shutdown(Shutdown::Both);
+		}
+		for connection in connections {
+			// a panic of a connection thread only affected its client
+			let _ = connection.thread.join();
+		}
+		result
+	}
+
+	/// Runs the server in a separate thread and returns a [`ServerHandle`] to stop it.
+	#[must_use]
+	pub fn spawn(self) -> ServerHandle {
+		let path = self.path.clone();
+		let stopped = self.stopped.clone();
+		ServerHandle {
+			thread: std::thread::spawn(move || self.run()),
+			path,
+			stopped,
+		}
+	}
+}
+
+/// Handle of a [`DataboardServer`] running in a separate thread, created by [`DataboardServer::spawn`].
+#[derive(Debug)]
+pub struct ServerHandle {
+	thread: JoinHandle<Result<()>>,
+	path: PathBuf,
+	stopped: Arc<AtomicBool>,
+}
+
+impl ServerHandle {
+	/// Stops the server, closes all its connections, waits for all its threads and removes the socket file.
+	/// # Errors
+	/// - [`Error::Remote`] if the server failed, see [`DataboardServer::run`].
+	pub fn shutdown(self) -> Result<()> {
+		self.stopped.store(true, Ordering::SeqCst);
+		// wake up the waiting accept, fails if the server has already stopped
+		let _ = UnixStream::connect(&self.path);
+		self.thread.join().map_err(|_| panicked())?
+	}
+}
+
+impl RemoteDataboard<UnixStream> {
+	/// Connects to the [`DataboardServer`] listening at `path`.
+	/// # Errors
+	/// - [`Error::Remote`] if the connection can not be established.
+	pub fn connect(path: impl AsRef<Path>, registry: Arc<Registry>) -> Result<Self> {
+		let stream = UnixStream::connect(path).map_err(io_error)?;
+		Ok(Self::new(stream, registry))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	// check, that the auto traits are available
+	const fn is_normal<T: Sized + Send + Sync>() {}
+
+	#[test]
+	const fn normal_types() {
+		is_normal::<DataboardServer>();
+		is_normal::<ServerHandle>();
+	}
+}
@@ -0,0 +1,250 @@
+// Copyright © 2025 Stephan Kunz
+//! Implementation of the change [`Journal`] of a [`Databoard`](crate::databoard::Databoard).
+
+use crate::{
+	ConstString,
+	sequence::{SequenceCounter, SequenceId},
+};
+use alloc::{collections::vec_deque::VecDeque, vec::Vec};
+use portable_atomic::{AtomicU64, AtomicUsize, Ordering};
+use spin::RwLock;
+
+// region:		--- Change
+/// The kind of a recorded [`Change`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Operation {
+	/// The entry has been created, including being moved or renamed to the key.
+	Create,
+	/// The entry has been replaced by a new value.
+	Update,
+	/// The entry has been modified via an [`EntryWriteGuard`](crate::entry::EntryWriteGuard).
+	Modify,
+	/// The entry has been deleted, including being moved or renamed away from the key.
+	Delete,
+}
+
+/// A single change of an entry recorded in the [`Journal`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Change {
+	/// The key of the entry within its [`Databoard`](crate::databoard::Databoard).
+	pub key: ConstString,
+	/// The kind of change.
+	pub operation: Operation,
+	/// The `sequence_id` of the entry after the change, '0' for deleted entries.
+	pub sequence_id: SequenceId,
+}
+
+/// The result of [`Databoard::changes_since`](crate::databoard::DataboardInner::changes_since).
+#[derive(Clone, Debug)]
+pub struct Changes {
+	/// The recorded changes, oldest first.
+	pub changes: Vec<Change>,
+	/// `false` if changes have been dropped from the journal since the requested cursor,
+	/// so the consumer has to resynchronize the whole board.
+	pub complete: bool,
+	/// The cursor to use for the next request.
+	pub cursor: u64,
+}
+// endregion:	--- Change
+
+// region:		--- Journal
+/// An append-only journal of [`Change`]s with a limited capacity.
+///
+/// Every change gets a position, the cursor, which is counted up by the [`Recorder`] even if the change is not kept.
+/// When the capacity is reached, the oldest changes are dropped.
+#[derive(Debug, Default)]
+pub struct Journal {
+	capacity: usize,
+	/// The cursor of the first kept change.
+	start: u64,
+	changes: VecDeque<Change>,
+}
+
+impl Journal {
+	/// Returns the cursor following the last kept change.
+	#[must_use]
+	fn end(&self) -> u64 {
+		self.start + self.changes.len() as u64
+	}
+
+	/// Returns the changes recorded since `cursor`, where `end` is the cursor of the next change to be recorded.
+	#[must_use]
+	pub fn changes_since(&self, cursor: u64, end: u64) -> Changes {
+		// all changes up to `end` must be kept, unless nothing changed since `cursor`
+		let complete = cursor == end || ((self.start..=end).contains(&cursor) && self.end() == end);
+		let skip = usize::try_from(cursor.saturating_sub(self.start)).unwrap_or(usize::MAX);
+		Changes {
+			changes: self.changes.iter().skip(skip).cloned().collect(),
+			complete,
+			cursor: end,
+		}
+	}
+
+	/// Appends the change at position `cursor`, dropping the oldest change if the capacity is reached.
+	/// If changes before `cursor` are missing, all kept changes are dropped.
+	pub fn push(&mut self, cursor: u64, change: Change) {
+		if self.capacity == 0 {
+			return;
+		}
+		if self.end() != cursor {
+			self.changes.clear();
+			self.start = cursor;
+		}
+		if self.changes.len() >= self.capacity {
+			self.changes.pop_front();
+			self.start += 1;
+		}
+		self.changes.push_back(change);
+	}
+
+	/// Sets the maximum number of kept changes, dropping the oldest changes if necessary.
+	/// A capacity of '0' disables the journal. `end` is the cursor of the next change to be recorded.
+	pub fn set_capacity(&mut self, capacity: usize, end: u64) {
+		if self.end() != end {
+			// changes have been counted while the journal was disabled
+			self.changes.clear();
+			self.start = end;
+		}
+		while self.changes.len() > capacity {
+			self.changes.pop_front();
+			self.start += 1;
+		}
+		self.capacity = capacity;
+		self.changes.shrink_to(capacity);
+	}
+}
+// endregion:	--- Journal
+
+// region:		--- Recorder
+/// Records the modifications of a [`Database`](crate::database::Database)
+/// by increasing its modification counter and appending to its [`Journal`].
+///
+/// The [`Journal`] is only locked while it is enabled, otherwise recording a change only updates atomic counters.
+#[derive(Debug, Default)]
+pub struct Recorder {
+	modifications: SequenceCounter,
+	/// The cursor of the next change to be recorded.
+	cursor: AtomicU64,
+	/// The capacity of the journal, '0' if it is disabled.
+	capacity: AtomicUsize,
+	journal: RwLock<Journal>,
+}
+
+impl Recorder {
+	/// Returns the changes recorded since `cursor`.
+	#[must_use]
+	pub fn changes_since(&self, cursor: u64) -> Changes {
+		let journal = self.journal.read();
+		journal.changes_since(cursor, self.cursor())
+	}
+
+	/// Returns the cursor of the next change to be recorded.
+	#[must_use]
+	pub fn cursor(&self) -> u64 {
+		self.cursor.load(Ordering::SeqCst)
+	}
+
+	/// Returns the modification counter.
+	#[must_use]
+	pub fn modifications(&self) -> SequenceId {
+		self.modifications.get()
+	}
+
+	/// Records a change of the entry stored under `key`.
+	pub fn record(&self, key: &ConstString, operation: Operation, sequence_id: SequenceId) {
+		self.modifications.increment();
+		if self.capacity.load(Ordering::SeqCst) == 0 {
+			// disabled, only the cursor is counted up without locking the journal
+			self.cursor.fetch_add(1, Ordering::SeqCst);
+			return;
+		}
+		let mut journal = self.journal.write();
+		let cursor = self.cursor.fetch_add(1, Ordering::SeqCst);
+		journal.push(
+			cursor,
+			Change {
+				key: key.clone(),
+				operation,
+				sequence_id,
+			},
+		);
+	}
+
+	/// Sets the capacity of the [`Journal`], a capacity of '0' disables it.
+	pub fn set_capacity(&self, capacity: usize) {
+		let mut journal = self.journal.write();
+		self.capacity.store(capacity, Ordering::SeqCst);
+		journal.set_capacity(capacity, self.cursor());
+	}
+}
+// endregion:	--- Recorder
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	// check, that the auto traits are available
+	const fn is_normal<T: Sized + Send + Sync>() {}
+
+	#[test]
+	const fn normal_types() {
+		is_normal::<Change>();
+		is_normal::<Changes>();
+		is_normal::<Journal>();
+		is_normal::<Operation>();
+		is_normal::<Recorder>();
+	}
+
+	#[test]
+	fn journal() {
+		let key: ConstString = "key".into();
+		let change = |i: SequenceId| Change {
+			key: key.clone(),
+			operation: Operation::Update,
+			sequence_id: i,
+		};
+		let recorder = Recorder::default();
+		recorder.set_capacity(3);
+		for i in 1..=5 {
+			recorder.record(&key, Operation::Update, i);
+		}
+		assert_eq!(recorder.cursor(), 5);
+		assert_eq!(recorder.modifications(), 5);
+
+		let changes = recorder.changes_since(3);
+		assert!(changes.complete);
+		assert_eq!(changes.cursor, 5);
+		assert_eq!(changes.changes, [change(4), change(5)]);
+
+		let changes = recorder.changes_since(1);
+		assert!(!changes.complete);
+		assert_eq!(changes.changes, [change(3), change(4), change(5)]);
+
+		let changes = recorder.changes_since(5);
+		assert!(changes.complete);
+		assert!(changes.changes.is_empty());
+
+		recorder.set_capacity(1);
+		assert_eq!(recorder.changes_since(4).changes, [change(5)]);
+		assert!(!recorder.changes_since(3).complete);
+
+		// a disabled journal only counts
+		recorder.set_capacity(0);
+		recorder.record(&key, Operation::Update, 6);
+		assert_eq!(recorder.cursor(), 6);
+		assert!(!recorder.changes_since(5).complete);
+		assert!(recorder.changes_since(6).complete);
+
+		// changes counted while disabled are not reported as complete after enabling again
+		recorder.set_capacity(2);
+		assert!(!recorder.changes_since(5).complete);
+		recorder.record(&key, Operation::Update, 7);
+		let changes = recorder.changes_since(6);
+		assert!(changes.complete);
+		assert_eq!(changes.changes, [change(7)]);
+
+		// a gap in the positions drops the kept changes
+		recorder.journal.write().push(8, change(9));
+		assert!(!recorder.changes_since(6).complete);
+	}
+}
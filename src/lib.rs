@@ -8,21 +8,58 @@ doc_comment::doctest!("../README.md");
 #[doc(hidden)]
 extern crate alloc;
 
+mod atomic;
+mod backoff;
+mod cloner;
+mod codec;
 mod database;
 mod databoard;
+mod defaults;
+mod diff;
 mod entry;
 mod error;
+#[cfg(all(feature = "std", unix))]
+mod ipc;
+mod journal;
+#[cfg(feature = "std")]
+mod mirror;
+mod overlay;
+mod port;
+#[cfg(feature = "std")]
+mod protocol;
 mod remappings;
+mod sequence;
+mod shard;
+mod view;
 
 // flatten
+pub use atomic::AtomicValue;
+#[cfg(feature = "std")]
+pub use backoff::Timeout;
+pub use backoff::{Backoff, Retries};
+pub use cloner::CloneRegistry;
+pub use codec::{Registry, Serial};
 pub use databoard::Databoard;
-pub use entry::{EntryReadGuard, EntryWriteGuard};
-pub use error::Error;
+pub use defaults::Defaults;
+pub use diff::{Diff, MergePolicy};
+pub use entry::{EntryReadGuard, EntryUpgradableGuard, EntryWriteGuard};
+pub use error::{Context, Error};
+#[cfg(all(feature = "std", unix))]
+pub use ipc::{DataboardServer, ServerHandle};
+pub use journal::{Change, Changes, Operation};
+#[cfg(feature = "std")]
+pub use mirror::Mirror;
+pub use overlay::Overlay;
+pub use port::{InputPort, OutputPort};
+#[cfg(feature = "std")]
+pub use protocol::{RemoteDataboard, Request, Response, Value, handle, read_frame, serve, write_frame};
 pub use remappings::{
 	Remappings, check_board_pointer, check_local_key, check_local_pointer, check_top_level_key, check_top_level_pointer,
 	is_board_pointer, is_const_assignment, is_local_pointer, is_top_level_pointer, strip_board_pointer, strip_local_pointer,
 	strip_top_level_pointer,
 };
+pub use sequence::SequenceId;
+pub use view::DataboardView;
 
 /// An immutable thread safe `String` type
 /// see: [Logan Smith](https://www.youtube.com/watch?v=A4cKi7PTJSs).
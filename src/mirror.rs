@@ -0,0 +1,142 @@
+// Copyright © 2025 Stephan Kunz
+//! Mirroring a remote [`Databoard`] into a local [`Databoard`].
+
+extern crate std;
+
+use crate::{ConstString, Databoard, Error, error::Result, protocol::RemoteDataboard};
+use alloc::collections::btree_set::BTreeSet;
+use std::io::{Read, Write};
+
+/// Mirrors the entries of a [`RemoteDataboard`] into a local [`Databoard`].
+///
+/// The mirror is updated by polling with [`Mirror::sync`]. Only the changes recorded in the journal of the
+/// remote [`Databoard`] are transferred, if the journal is disabled or has dropped changes in the meantime,
+/// all entries are transferred again. Entries of types not registered in the [`Registry`](crate::Registry)
+/// are not mirrored.
+///
+/// Changes of the local [`Databoard`] are not propagated, they may be overwritten by the next [`Mirror::sync`].
+/// Changes of the remote [`Databoard`] can be made via [`Mirror::remote`].
+pub struct Mirror<S: Read + Write> {
+	remote: RemoteDataboard<S>,
+	board: Databoard,
+	/// The journal cursor of the remote [`Databoard`], `None` before the first synchronization.
+	cursor: Option<u64>,
+}
+
+impl<S: Read + Write> core::fmt::Debug for Mirror<S> {
+	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+		write!(f, "Mirror {{ {:?}, cursor: {:?} }}", &self.remote, &self.cursor)
+	}
+}
+
+impl<S: Read + Write> Mirror<S> {
+	/// Creates a [`Mirror`] of `remote` into `board`.
+	/// The entries of `board` itself will be replaced with the first [`Mirror::sync`].
+	pub const fn new(remote: RemoteDataboard<S>, board: Databoard) -> Self {
+		Self {
+			remote,
+			board,
+			cursor: None,
+		}
+	}
+
+	/// Returns the local [`Databoard`].
+	#[must_use]
+	pub const fn board(&self) -> &Databoard {
+		&self.board
+	}
+
+	/// Transfers the entry stored under `key` from the remote to the local [`Databoard`].
+	/// Returns `false` if the entry does not exist or has a type, which is not registered.
+	fn fetch(&mut self, key: &str) -> Result<bool> {
+		match self.remote.get_value(key) {
+			Ok(value) => {
+				let registry = self.remote.registry();
+				match registry.set(&self.board, key, &value.type_name, &value.bytes) {
+					// the entry has been replaced with a value of another type
+					Err(Error::WrongType { .. }) => {
+						self.board.remove(key)?;
+						registry.set(&self.board, key, &value.type_name, &value.bytes)?;
+					}
+					result => {
+						result?;
+					}
+				}
+				Ok(true)
+			}
+			Err(Error::NotFound { .. } | Error::Unregistered { .. }) => match self.board.remove(key) {
+				Ok(_) | Err(Error::NotFound { .. }) => Ok(false),
+				Err(error) => Err(error),
+			},
+			Err(error) => Err(error),
+		}
+	}
+
+	/// Returns the [`RemoteDataboard`] to access the remote [`Databoard`] directly.
+	pub const fn remote(&mut self) -> &mut RemoteDataboard<S> {
+		&mut self.remote
+	}
+
+	/// Replaces all entries of the local [`Databoard`] with the entries of the remote [`Databoard`].
+	/// Returns the number of transferred entries.
+	/// # Errors
+	/// - [`Error::InUse`]  if a local entry to replace is still referenced elsewhere.
+	/// - [`Error::Remote`] if the communication fails.
+	pub fn resync(&mut self) -> Result<usize> {
+		// fetch the cursor first, so that no change gets lost
+		let cursor = self.remote.subscribe(u64::MAX)?.cursor;
+		let keys: BTreeSet<ConstString> = self.remote.list()?.into_iter().collect();
+		for key in self.board.keys() {
+			if !keys.contains(&key) {
+				self.board.remove(&key)?;
+			}
+		}
+		let mut count = 0;
+		for key in keys {
+			if self.fetch(&key)? {
+				count += 1;
+			}
+		}
+		self.cursor = Some(cursor);
+		Ok(count)
+	}
+
+	/// Transfers the changes of the remote [`Databoard`] since the last synchronization.
+	/// Returns the number of changed entries.
+	/// # Errors
+	/// - [`Error::InUse`]  if a local entry to replace is still referenced elsewhere.
+	/// - [`Error::Remote`] if the communication fails.
+	pub fn sync(&mut self) -> Result<usize> {
+		let Some(cursor) = self.cursor else {
+			return self.resync();
+		};
+		let changes = self.remote.subscribe(cursor)?;
+		if !changes.complete {
+			return self.resync();
+		}
+		// the current value is fetched, so multiple changes of an entry are transferred only once
+		let keys: BTreeSet<ConstString> = changes
+			.changes
+			.into_iter()
+			.map(|change| change.key)
+			.collect();
+		for key in &keys {
+			self.fetch(key)?;
+		}
+		self.cursor = Some(changes.cursor);
+		Ok(keys.len())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	// check, that the auto traits are available
+	const fn is_normal<T: Sized + Send + Sync>() {}
+
+	#[test]
+	const fn normal_types() {
+		is_normal::<Mirror<std::net::TcpStream>>();
+	}
+}
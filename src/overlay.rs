@@ -0,0 +1,316 @@
+// Copyright © 2025 Stephan Kunz
+//! Implements the [`Overlay`], a temporary layer on top of a [`Databoard`].
+
+use crate::{
+	ConstString, Databoard, Error,
+	database::Database,
+	databoard::DataboardInner,
+	entry::{EntryReadGuard, EntryWriteGuard},
+	error::Result,
+	remappings::{Remappings, check_local_key},
+	sequence::SequenceId,
+};
+use alloc::{collections::btree_map::BTreeMap, vec::Vec};
+use core::any::Any;
+use spin::RwLock;
+
+/// Type erased transfer of a changed value into its target [`Databoard`].
+type Commit = fn(&Database, &DataboardInner, &str) -> Result<()>;
+
+/// A target entry, identified by the index of its [`Layer`] and the key valid there.
+type Target = (usize, ConstString);
+
+/// A pending change of an [`Overlay`].
+#[derive(Clone, Copy)]
+enum Pending {
+	/// The value stored in the overlay replaces the value of the target.
+	Set(Commit),
+	/// The target entry is deleted.
+	Delete,
+}
+
+/// Transfers the value of type `T` stored under `key` in `changes` to `key` in `target`.
+fn commit<T: Any + Send + Sync>(changes: &Database, target: &DataboardInner, key: &str) -> Result<()> {
+	// check the type before removing the value from the overlay
+	target.database.contains::<T>(key)?;
+	let value = changes.delete::<T>(key)?;
+	target.database.set(key, value)?;
+	Ok(())
+}
+
+/// A [`Databoard`] the keys of an [`Overlay`] can be resolved to, together with the values changed for it.
+struct Layer {
+	/// The target [`Databoard`].
+	board: Databoard,
+	/// The changed values, stored under the keys valid in `board`.
+	changes: Database,
+}
+
+/// A temporary layer on top of a parent [`Databoard`].
+///
+/// Keys are resolved like in a [`Databoard`] created with [`Databoard::with`] using `autoremap`,
+/// local keys starting with `_` are resolved against the parent, as the overlay has no entries of its own.
+/// Changes are tracked per resolved target entry, so all keys resolving to the same entry,
+/// e.g. `x`, `@x` or a remapped alias, see the same change.
+///
+/// Reads see the changes made in the overlay, otherwise the entries of the parent.
+/// Writes only change the overlay, an entry of the parent is copied into the overlay before it is modified.
+/// The changes are pushed into the parent with [`Overlay::commit`] or thrown away with [`Overlay::discard`].
+/// Dropping an [`Overlay`] discards all pending changes.
+///
+/// An [`Overlay`] is a type of its own instead of a mode of [`Databoard::with_parent`],
+/// because a [`Databoard`] hands out references and guards to its entries and is shared with its children,
+/// so neither copy on write nor throwing away changes could be guaranteed for a [`Databoard`].
+/// For the same reason the [`Overlay`] does not show up in [`Databoard::children`] of its parent.
+pub struct Overlay {
+	/// Resolves the keys, it is not registered as a child of the parent.
+	board: Databoard,
+	/// The parent and its ancestors, indexed by their level.
+	layers: Vec<Layer>,
+	/// The pending changes.
+	pending: RwLock<BTreeMap<Target, Pending>>,
+}
+
+impl core::fmt::Debug for Overlay {
+	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+		write!(f, "Overlay {{ ")?;
+		let pending = self.pending.read();
+		let keys: Vec<&Target> = pending.keys().collect();
+		write!(f, "pending: {keys:?}")?;
+		drop(pending);
+		write!(f, ", {:?} }}", &self.board)
+	}
+}
+
+impl Overlay {
+	/// Creates an [`Overlay`] on top of `parent`.
+	#[must_use]
+	pub fn new(parent: &Databoard) -> Self {
+		Self::with(parent, None)
+	}
+
+	/// Creates an [`Overlay`] on top of `parent`, using the given `remappings` to resolve the keys.
+	#[must_use]
+	pub fn with(parent: &Databoard, remappings: Option<Remappings>) -> Self {
+		let mut layers = Vec::new();
+		let mut board = Some(parent);
+		while let Some(current) = board {
+			layers.push(Layer {
+				board: current.clone(),
+				changes: Database::new(1),
+			});
+			board = current.parent();
+		}
+		layers.reverse();
+		Self {
+			board: Databoard::detached(parent.clone(), remappings),
+			layers,
+			pending: RwLock::default(),
+		}
+	}
+
+	/// Pushes all pending changes into the parent [`Databoard`] respecting the remappings,
+	/// so the overlay is empty afterwards.
+	///
+	/// A change is only removed from the overlay after it has been pushed,
+	/// so if a change can not be pushed, it and the remaining changes stay pending.
+	/// # Errors
+	/// - [`Error::InUse`]     if an entry is still referenced elsewhere.
+	/// - [`Error::WrongType`] if an entry of the parent has meanwhile been replaced with a different type.
+	pub fn commit(&self) -> Result<()> {
+		let mut pending = self.pending.write();
+		while let Some(entry) = pending.first_entry() {
+			let (index, key) = entry.key();
+			let layer = self
+				.layers
+				.get(*index)
+				.ok_or_else(|| Error::Unreachable(file!().into(), line!()))?;
+			match entry.get() {
+				Pending::Set(commit) => commit(&layer.changes, &layer.board, key)?,
+				Pending::Delete => match layer.board.database.remove(key) {
+					Ok(_) | Err(Error::NotFound { .. }) => {}
+					Err(error) => return Err(error),
+				},
+			}
+			entry.remove();
+		}
+		drop(pending);
+		Ok(())
+	}
+
+	/// Returns `true` if a certain `key` is available, otherwise `false`.
+	#[must_use]
+	pub fn contains_key(&self, key: &str) -> bool {
+		match self.lookup(key) {
+			Ok((.., Some(Pending::Set(_)))) => true,
+			Ok((.., Some(Pending::Delete))) | Err(_) => false,
+			Ok((.., None)) => self.resolver(key).contains_key(key),
+		}
+	}
+
+	/// Deletes the entry stored under `key` from the overlay.
+	/// The entry of the parent is deleted with the next [`Overlay::commit`].
+	/// # Errors
+	/// - [`Error::Assignment`] if the remapping contains an assignment of a `str` value.
+	/// - [`Error::InUse`]      if the entry of the overlay is still referenced elsewhere.
+	/// - [`Error::NotFound`]   if `key` is not contained.
+	pub fn delete(&self, key: &str) -> Result<()> {
+		let (layer, target) = self.target(key)?;
+		let mut pending = self.pending.write();
+		match pending.get(&target) {
+			Some(Pending::Set(_)) => {
+				layer.changes.remove(&target.1)?;
+			}
+			Some(Pending::Delete) => return Err(Error::not_found(key)),
+			None => {
+				if !self.resolver(key).contains_key(key) {
+					return Err(Error::not_found(key));
+				}
+			}
+		}
+		pending.insert(target, Pending::Delete);
+		drop(pending);
+		Ok(())
+	}
+
+	/// Throws away all pending changes.
+	pub fn discard(&self) {
+		let mut pending = self.pending.write();
+		pending.clear();
+		for layer in &self.layers {
+			layer.changes.clear();
+		}
+		drop(pending);
+	}
+
+	/// Returns a copy of the value of type `T` stored under `key`.
+	/// # Errors
+	/// - [`Error::Assignment`] if the remapping contains an assignment of a `str` value.
+	/// - [`Error::NotFound`]   if `key` is not contained.
+	/// - [`Error::WrongType`]  if the entry has not the expected type `T`.
+	pub fn get<T: Any + Clone + Send + Sync>(&self, key: &str) -> Result<T> {
+		match self.lookup(key)? {
+			(layer, (_, target_key), Some(Pending::Set(_))) => layer.changes.read(&target_key),
+			(.., Some(Pending::Delete)) => Err(Error::not_found(key)),
+			(.., None) => self.resolver(key).get(key),
+		}
+	}
+
+	/// Returns a read/write guard to the `T` of the entry stored under `key`.
+	/// An entry of the parent is copied into the overlay first.
+	///
+	/// You need to drop the received [`EntryWriteGuard`] before using `commit`, `delete` or `discard`.
+	/// # Errors
+	/// - [`Error::Assignment`] if the remapping contains an assignment of a `str` value.
+	/// - [`Error::NotFound`]   if `key` is not contained.
+	/// - [`Error::WrongType`]  if the entry has not the expected type `T`.
+	pub fn get_mut_ref<T: Any + Clone + Send + Sync>(&self, key: &str) -> Result<EntryWriteGuard<T>> {
+		let (layer, (_, target_key), pending) = self.lookup(key)?;
+		if pending.is_none() {
+			// copy on write
+			let value = self.resolver(key).get::<T>(key)?;
+			self.set(key, value)?;
+		}
+		layer.changes.get_mut_ref(&target_key)
+	}
+
+	/// Returns a read guard to the `T` of the entry stored under `key`.
+	///
+	/// You need to drop the received [`EntryReadGuard`] before using `commit`, `delete` or `discard`.
+	/// # Errors
+	/// - [`Error::Assignment`] if the remapping contains an assignment of a `str` value.
+	/// - [`Error::LockFree`]   if the entry is stored lock-free.
+	/// - [`Error::NotFound`]   if `key` is not contained.
+	/// - [`Error::WrongType`]  if the entry has not the expected type `T`.
+	pub fn get_ref<T: Any + Send + Sync>(&self, key: &str) -> Result<EntryReadGuard<T>> {
+		match self.lookup(key)? {
+			(layer, (_, target_key), Some(Pending::Set(_))) => layer.changes.get_ref(&target_key),
+			(.., Some(Pending::Delete)) => Err(Error::not_found(key)),
+			(.., None) => self.resolver(key).get_ref(key),
+		}
+	}
+
+	/// Returns `true` if there are no pending changes, otherwise `false`.
+	#[must_use]
+	pub fn is_empty(&self) -> bool {
+		self.pending.read().is_empty()
+	}
+
+	/// Returns the number of pending changes.
+	#[must_use]
+	pub fn len(&self) -> usize {
+		self.pending.read().len()
+	}
+
+	/// Resolves `key` to its target entry and returns the pending change for it.
+	fn lookup(&self, key: &str) -> Result<(&Layer, Target, Option<Pending>)> {
+		let (layer, target) = self.target(key)?;
+		let pending = self.pending.read().get(&target).copied();
+		Ok((layer, target, pending))
+	}
+
+	/// Returns the [`Databoard`] resolving `key`, local keys are resolved by the parent.
+	fn resolver(&self, key: &str) -> &Databoard {
+		if check_local_key(key).is_ok() {
+			self.layers
+				.last()
+				.map_or(&self.board, |layer| &layer.board)
+		} else {
+			&self.board
+		}
+	}
+
+	/// Returns the sequence id of an entry.
+	/// Entries changed in the overlay have their own sequence id starting with '1'.
+	/// # Errors
+	/// - [`Error::Assignment`] if the remapping contains an assignment of a `str` value.
+	/// - [`Error::NotFound`]   if `key` is not contained.
+	pub fn sequence_id(&self, key: &str) -> Result<SequenceId> {
+		match self.lookup(key)? {
+			(layer, (_, target_key), Some(Pending::Set(_))) => layer.changes.sequence_id(&target_key),
+			(.., Some(Pending::Delete)) => Err(Error::not_found(key)),
+			(.., None) => self.resolver(key).sequence_id(key),
+		}
+	}
+
+	/// Stores `value` under `key` in the overlay.
+	/// # Errors
+	/// - [`Error::Assignment`] if the remapping contains an assignment of a `str` value.
+	/// - [`Error::WrongType`]  if the entry of the overlay or the parent has a different type.
+	pub fn set<T: Any + Send + Sync>(&self, key: &str, value: T) -> Result<()> {
+		let (layer, target) = self.target(key)?;
+		layer.board.database.contains::<T>(&target.1)?;
+		let mut pending = self.pending.write();
+		layer.changes.set(target.1.clone(), value)?;
+		pending.insert(target, Pending::Set(commit::<T>));
+		drop(pending);
+		Ok(())
+	}
+
+	/// Resolves `key` to the [`Layer`] responsible for it and the target entry.
+	fn target(&self, key: &str) -> Result<(&Layer, Target)> {
+		let (board, target_key) = self.resolver(key).resolve(key)?;
+		self.layers
+			.iter()
+			.enumerate()
+			.find(|(_, layer)| {
+				let inner: &DataboardInner = &layer.board;
+				core::ptr::eq(inner, board)
+			})
+			.map(|(index, layer)| (layer, (index, target_key)))
+			.ok_or_else(|| Error::Unreachable(file!().into(), line!()))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	// check, that the auto traits are available
+	const fn is_normal<T: Sized + Send + Sync>() {}
+
+	#[test]
+	const fn normal_types() {
+		is_normal::<Overlay>();
+	}
+}
@@ -0,0 +1,179 @@
+// Copyright © 2025 Stephan Kunz
+//! Implements typed [`InputPort`]s and [`OutputPort`]s to access a [`Databoard`].
+
+use crate::{ConstString, Databoard, Error, error::Result};
+use alloc::boxed::Box;
+use core::{any::Any, marker::PhantomData, str::FromStr};
+
+/// Creates an [`Error::Port`].
+fn port_error(port: &ConstString, remapped: ConstString, level: usize, source: Error) -> Error {
+	Error::Port {
+		port: port.clone(),
+		remapped,
+		level,
+		source: Box::new(source),
+	}
+}
+
+/// Adds the port information to an error of resolving the port name.
+fn resolve_error(port: &ConstString, board: &Databoard, source: Error) -> Error {
+	let remapped = match &source {
+		Error::Assignment { value, .. } => value.clone(),
+		Error::NoParent { remapped, .. } => remapped.clone(),
+		_ => port.clone(),
+	};
+	port_error(port, remapped, board.level(), source)
+}
+
+// region:		--- InputPort
+/// A typed input port reading a value of type `T` from a [`Databoard`].
+///
+/// The port name is resolved using the remapping rules of the [`Databoard`].
+/// If the port is remapped to a constant assignment, the value is parsed via [`FromStr`].
+pub struct InputPort<T> {
+	name: ConstString,
+	board: Databoard,
+	marker: PhantomData<fn() -> T>,
+}
+
+impl<T> Clone for InputPort<T> {
+	fn clone(&self) -> Self {
+		Self {
+			name: self.name.clone(),
+			board: self.board.clone(),
+			marker: PhantomData,
+		}
+	}
+}
+
+impl<T> core::fmt::Debug for InputPort<T> {
+	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+		write!(
+			f,
+			"InputPort {{ name: {}, type: {} }}",
+			&self.name,
+			core::any::type_name::<T>()
+		)
+	}
+}
+
+impl<T> InputPort<T> {
+	/// Creates an [`InputPort`] named `name` reading from `board`.
+	#[must_use]
+	pub fn new(name: &str, board: &Databoard) -> Self {
+		Self {
+			name: name.into(),
+			board: board.clone(),
+			marker: PhantomData,
+		}
+	}
+
+	/// Returns the name of the port.
+	#[must_use]
+	pub fn name(&self) -> &str {
+		&self.name
+	}
+}
+
+impl<T: Any + Clone + FromStr + Send + Sync> InputPort<T> {
+	/// Returns a copy of the value of the port.
+	///
+	/// The value is read like with [`Databoard::get`](crate::databoard::DataboardInner::get),
+	/// so missing entries fall back to the [`Defaults`](crate::Defaults) along the remapping chain.
+	/// # Errors
+	/// - [`Error::Port`] wrapping the reason, if the value can not be read or the constant assignment can not be parsed.
+	pub fn get(&self) -> Result<T> {
+		match self.board.get(&self.name) {
+			Ok(value) => Ok(value),
+			Err(Error::Assignment { key, value }) => value
+				.parse()
+				.map_err(|_| port_error(&self.name, value.clone(), self.board.level(), Error::Parse { key, value })),
+			Err(source) => match self.board.resolve(&self.name) {
+				Ok((board, key)) => Err(port_error(&self.name, key, board.level(), source)),
+				Err(_) => Err(resolve_error(&self.name, &self.board, source)),
+			},
+		}
+	}
+}
+// endregion:	--- InputPort
+
+// region:		--- OutputPort
+/// A typed output port writing a value of type `T` into a [`Databoard`].
+///
+/// The port name is resolved using the remapping rules of the [`Databoard`].
+/// Ports remapped to a constant assignment can not be written.
+pub struct OutputPort<T> {
+	name: ConstString,
+	board: Databoard,
+	marker: PhantomData<fn(T)>,
+}
+
+impl<T> Clone for OutputPort<T> {
+	fn clone(&self) -> Self {
+		Self {
+			name: self.name.clone(),
+			board: self.board.clone(),
+			marker: PhantomData,
+		}
+	}
+}
+
+impl<T> core::fmt::Debug for OutputPort<T> {
+	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+		write!(
+			f,
+			"OutputPort {{ name: {}, type: {} }}",
+			&self.name,
+			core::any::type_name::<T>()
+		)
+	}
+}
+
+impl<T> OutputPort<T> {
+	/// Creates an [`OutputPort`] named `name` writing into `board`.
+	#[must_use]
+	pub fn new(name: &str, board: &Databoard) -> Self {
+		Self {
+			name: name.into(),
+			board: board.clone(),
+			marker: PhantomData,
+		}
+	}
+
+	/// Returns the name of the port.
+	#[must_use]
+	pub fn name(&self) -> &str {
+		&self.name
+	}
+}
+
+impl<T: Any + Send + Sync> OutputPort<T> {
+	/// Stores `value` as value of the port and returns an eventually existing value.
+	/// # Errors
+	/// - [`Error::Port`] wrapping the reason, if the value can not be stored.
+	pub fn set(&self, value: T) -> Result<Option<T>> {
+		let (board, key) = self
+			.board
+			.resolve(&self.name)
+			.map_err(|source| resolve_error(&self.name, &self.board, source))?;
+		board
+			.database
+			.set(key.clone(), value)
+			.map_err(|source| port_error(&self.name, key, board.level(), source))
+	}
+}
+// endregion:	--- OutputPort
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	// check, that the auto traits are available
+	const fn is_normal<T: Sized + Send + Sync>() {}
+
+	#[test]
+	const fn normal_types() {
+		is_normal::<InputPort<i32>>();
+		is_normal::<OutputPort<i32>>();
+	}
+}
@@ -0,0 +1,701 @@
+// Copyright © 2025 Stephan Kunz
+//! Framed protocol to access a [`Databoard`] over a byte stream.
+//!
+//! Every message is a frame consisting of the length of the payload as `u32` in little endian byte order,
+//! followed by the payload. The payload starts with a one byte code, the following fields are serialized
+//! as defined by [`Serial`](crate::codec::Serial), strings and values are prefixed with their length as `u64`.
+//!
+//! | Request       | Code | Fields                        | Response      |
+//! |---------------|------|-------------------------------|---------------|
+//! | `ContainsKey` | 1    | `key`                         | `Bool`        |
+//! | `Delete`      | 2    | `key`, `type_name`            | `Value`       |
+//! | `Get`         | 3    | `key`                         | `Value`       |
+//! | `SequenceId`  | 4    | `key`                         | `SequenceId`  |
+//! | `Set`         | 5    | `key`, `type_name`, `value`   | `Value`       |
+//! | `List`        | 6    |                               | `Keys`        |
+//! | `Subscribe`   | 7    | `cursor` as `u64`             | `Changes`     |
+//!
+//! | Response      | Code | Fields                                                              |
+//! |---------------|------|---------------------------------------------------------------------|
+//! | `Bool`        | 1    | `bool`                                                              |
+//! | `Error`       | 2    | error code as `u8`, `key` or message                                |
+//! | `SequenceId`  | 3    | `u64`                                                               |
+//! | `Value`       | 4    | `bool` whether a value follows, `type_name`, `value`                |
+//! | `Keys`        | 5    | number of keys as `u64`, `key`s                                     |
+//! | `Changes`     | 6    | `complete`, `cursor`, number of changes as `u64`, changes           |
+//!
+//! A change consists of `key`, the operation as `u8` (`0` create, `1` update, `2` modify, `3` delete)
+//! and the `sequence_id` as `u64`.
+//!
+//! The error codes are `0` for [`Error::Remote`] carrying a message, `1` [`Error::AlreadyExists`],
+//! `2` [`Error::InUse`], `3` [`Error::IsLocked`], `4` [`Error::LockFree`], `5` [`Error::NotFound`],
+//! `6` [`Error::Unregistered`] and `7` [`Error::WrongType`], each carrying the `key` without a context.
+//! Other errors are transferred as [`Error::Remote`] with their message.
+//!
+//! A subscription is polled: [`Request::Subscribe`] returns the changes recorded in the journal of the
+//! served [`Databoard`] since `cursor`, so the journal has to be enabled with
+//! [`set_journal_capacity`](crate::databoard::DataboardInner::set_journal_capacity).
+
+extern crate std;
+
+use crate::{
+	ConstString, Databoard, Error,
+	codec::{Registry, Serial, deserialize, serialize},
+	error::Result,
+	journal::{Change, Changes, Operation},
+	sequence::SequenceId,
+};
+use alloc::{string::String, sync::Arc, vec::Vec};
+use core::any::Any;
+use std::io::{ErrorKind, Read, Write};
+
+/// Maximum accepted payload size of a frame.
+const MAX_FRAME: usize = 1 << 24;
+
+// region:		--- helpers
+/// Converts an I/O error into an [`Error::Remote`].
+#[allow(clippy::needless_pass_by_value)]
+fn io_error(error: std::io::Error) -> Error {
+	Error::Remote {
+		message: alloc::format!("{error}").into(),
+	}
+}
+
+/// Returns an [`Error::Remote`] for a malformed message.
+fn malformed() -> Error {
+	Error::Remote {
+		message: "malformed message".into(),
+	}
+}
+
+/// Writes `payload` as a frame to `stream`.
+/// # Errors
+/// - [`Error::Remote`] if writing fails.
+pub fn write_frame(stream: &mut impl Write, payload: &[u8]) -> Result<()> {
+	let len = u32::try_from(payload.len())
+		.ok()
+		.filter(|len| *len as usize <= MAX_FRAME)
+		.ok_or_else(|| Error::Remote {
+			message: "message too large".into(),
+		})?;
+	stream
+		.write_all(&len.to_le_bytes())
+		.and_then(|()| stream.write_all(payload))
+		.and_then(|()| stream.flush())
+		.map_err(io_error)
+}
+
+/// Reads the payload of a frame from `stream`, returns `None` if the stream has been closed before the frame.
+/// # Errors
+/// - [`Error::Remote`] if reading fails or the frame is too large.
+pub fn read_frame(stream: &mut impl Read) -> Result<Option<Vec<u8>>> {
+	let mut len = [0_u8; 4];
+	match stream.read_exact(&mut len) {
+		Ok(()) => {}
+		Err(error) if error.kind() == ErrorKind::UnexpectedEof => return Ok(None),
+		Err(error) => return Err(io_error(error)),
+	}
+	let len = u32::from_le_bytes(len) as usize;
+	if len > MAX_FRAME {
+		return Err(Error::Remote {
+			message: "message too large".into(),
+		});
+	}
+	let mut payload = alloc::vec![0_u8; len];
+	stream
+		.read_exact(&mut payload)
+		.map_err(io_error)?;
+	Ok(Some(payload))
+}
+
+/// Serializes a string.
+fn put_str(out: &mut Vec<u8>, value: &str) {
+	value.len().serialize(out);
+	out.extend_from_slice(value.as_bytes());
+}
+
+/// Deserializes a string.
+fn get_str(bytes: &mut &[u8]) -> Result<ConstString> {
+	String::deserialize(bytes)
+		.map(Into::into)
+		.ok_or_else(malformed)
+}
+
+/// Deserializes a `T`.
+fn get<T: Serial>(bytes: &mut &[u8]) -> Result<T> {
+	T::deserialize(bytes).ok_or_else(malformed)
+}
+
+/// Serializes a [`Change`].
+fn put_change(out: &mut Vec<u8>, change: &Change) {
+	put_str(out, &change.key);
+	let operation: u8 = match change.operation {
+		Operation::Create => 0,
+		Operation::Update => 1,
+		Operation::Modify => 2,
+		Operation::Delete => 3,
+	};
+	operation.serialize(out);
+	change.sequence_id.serialize(out);
+}
+
+/// Deserializes a [`Change`].
+fn get_change(bytes: &mut &[u8]) -> Result<Change> {
+	let key = get_str(bytes)?;
+	let operation = match get::<u8>(bytes)? {
+		0 => Operation::Create,
+		1 => Operation::Update,
+		2 => Operation::Modify,
+		3 => Operation::Delete,
+		_ => return Err(malformed()),
+	};
+	Ok(Change {
+		key,
+		operation,
+		sequence_id: get::<SequenceId>(bytes)?,
+	})
+}
+// endregion:	--- helpers
+
+// region:		--- messages
+/// A serialized value together with the name of its registered type.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Value {
+	/// The name of the type the value is registered with.
+	pub type_name: ConstString,
+	/// The serialized value.
+	pub bytes: Vec<u8>,
+}
+
+impl Value {
+	fn serialize(&self, out: &mut Vec<u8>) {
+		put_str(out, &self.type_name);
+		self.bytes.serialize(out);
+	}
+
+	fn deserialize(bytes: &mut &[u8]) -> Result<Self> {
+		Ok(Self {
+			type_name: get_str(bytes)?,
+			bytes: get(bytes)?,
+		})
+	}
+}
+
+/// A request to a remote [`Databoard`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Request {
+	/// Checks whether `key` exists.
+	ContainsKey {
+		/// Key of the entry.
+		key: ConstString,
+	},
+	/// Deletes the entry stored under `key`, if it has the type registered as `type_name`.
+	Delete {
+		/// Key of the entry.
+		key: ConstString,
+		/// The name of the expected type.
+		type_name: ConstString,
+	},
+	/// Reads the entry stored under `key`.
+	Get {
+		/// Key of the entry.
+		key: ConstString,
+	},
+	/// Reads the sequence id of the entry stored under `key`.
+	SequenceId {
+		/// Key of the entry.
+		key: ConstString,
+	},
+	/// Stores `value` under `key`.
+	Set {
+		/// Key of the entry.
+		key: ConstString,
+		/// The value to store.
+		value: Value,
+	},
+	/// Lists the keys of the entries stored in the [`Databoard`] itself.
+	List,
+	/// Polls the changes recorded since `cursor`.
+	Subscribe {
+		/// Cursor returned by the previous poll.
+		cursor: u64,
+	},
+}
+
+impl Request {
+	/// Serializes the request into a payload.
+	#[must_use]
+	pub fn encode(&self) -> Vec<u8> {
+		let mut out = Vec::new();
+		match self {
+			Self::ContainsKey { key } => {
+				out.push(1);
+				put_str(&mut out, key);
+			}
+			Self::Delete { key, type_name } => {
+				out.push(2);
+				put_str(&mut out, key);
+				put_str(&mut out, type_name);
+			}
+			Self::Get { key } => {
+				out.push(3);
+				put_str(&mut out, key);
+			}
+			Self::SequenceId { key } => {
+				out.push(4);
+				put_str(&mut out, key);
+			}
+			Self::Set { key, value } => {
+				out.push(5);
+				put_str(&mut out, key);
+				value.serialize(&mut out);
+			}
+			Self::List => out.push(6),
+			Self::Subscribe { cursor } => {
+				out.push(7);
+				cursor.serialize(&mut out);
+			}
+		}
+		out
+	}
+
+	/// Deserializes a request from a payload.
+	/// # Errors
+	/// - [`Error::Remote`] if the payload is malformed.
+	pub fn decode(mut payload: &[u8]) -> Result<Self> {
+		let bytes = &mut payload;
+		let request = match get::<u8>(bytes)? {
+			1 => Self::ContainsKey { key: get_str(bytes)? },
+			2 => Self::Delete {
+				key: get_str(bytes)?,
+				type_name: get_str(bytes)?,
+			},
+			3 => Self::Get { key: get_str(bytes)? },
+			4 => Self::SequenceId { key: get_str(bytes)? },
+			5 => Self::Set {
+				key: get_str(bytes)?,
+				value: Value::deserialize(bytes)?,
+			},
+			6 => Self::List,
+			7 => Self::Subscribe { cursor: get(bytes)? },
+			_ => return Err(malformed()),
+		};
+		if bytes.is_empty() { Ok(request) } else { Err(malformed()) }
+	}
+}
+
+/// A response from a remote [`Databoard`].
+#[derive(Debug)]
+pub enum Response {
+	/// Result of [`Request::ContainsKey`].
+	Bool(bool),
+	/// The request failed.
+	Error(Error),
+	/// Result of [`Request::SequenceId`].
+	SequenceId(u64),
+	/// Result of [`Request::Delete`], [`Request::Get`] and [`Request::Set`].
+	Value(Option<Value>),
+	/// Result of [`Request::List`].
+	Keys(Vec<ConstString>),
+	/// Result of [`Request::Subscribe`].
+	Changes(Changes),
+}
+
+impl Response {
+	/// Serializes the response into a payload.
+	#[must_use]
+	pub fn encode(&self) -> Vec<u8> {
+		let mut out = Vec::new();
+		match self {
+			Self::Bool(value) => {
+				out.push(1);
+				value.serialize(&mut out);
+			}
+			Self::Error(error) => {
+				out.push(2);
+				let (code, text) = match error {
+					Error::AlreadyExists { key } => (1, key.clone()),
+					Error::InUse { key } => (2, key.clone()),
+					Error::IsLocked { key } => (3, key.clone()),
+					Error::LockFree { key } => (4, key.clone()),
+					Error::NotFound { key, .. } => (5, key.clone()),
+					Error::Unregistered { key } => (6, key.clone()),
+					Error::WrongType { key, .. } => (7, key.clone()),
+					Error::Remote { message } => (0, message.clone()),
+					other => (0, alloc::format!("{other}").into()),
+				};
+				out.push(code);
+				put_str(&mut out, &text);
+			}
+			Self::SequenceId(value) => {
+				out.push(3);
+				value.serialize(&mut out);
+			}
+			Self::Value(value) => {
+				out.push(4);
+				value.is_some().serialize(&mut out);
+				if let Some(value) = value {
+					value.serialize(&mut out);
+				}
+			}
+			Self::Keys(keys) => {
+				out.push(5);
+				keys.len().serialize(&mut out);
+				for key in keys {
+					put_str(&mut out, key);
+				}
+			}
+			Self::Changes(changes) => {
+				out.push(6);
+				changes.complete.serialize(&mut out);
+				changes.cursor.serialize(&mut out);
+				changes.changes.len().serialize(&mut out);
+				for change in &changes.changes {
+					put_change(&mut out, change);
+				}
+			}
+		}
+		out
+	}
+
+	/// Deserializes a response from a payload.
+	/// # Errors
+	/// - [`Error::Remote`] if the payload is malformed.
+	pub fn decode(mut payload: &[u8]) -> Result<Self> {
+		let bytes = &mut payload;
+		let response = match get::<u8>(bytes)? {
+			1 => Self::Bool(get(bytes)?),
+			2 => {
+				let code = get::<u8>(bytes)?;
+				let key = get_str(bytes)?;
+				Self::Error(match code {
+					0 => Error::Remote { message: key },
+					1 => Error::AlreadyExists { key },
+					2 => Error::InUse { key },
+					3 => Error::IsLocked { key },
+					4 => Error::LockFree { key },
+					5 => Error::not_found(key),
+					6 => Error::Unregistered { key },
+					7 => Error::WrongType { key, context: None },
+					_ => return Err(malformed()),
+				})
+			}
+			3 => Self::SequenceId(get(bytes)?),
+			4 => {
+				if get::<bool>(bytes)? {
+					Self::Value(Some(Value::deserialize(bytes)?))
+				} else {
+					Self::Value(None)
+				}
+			}
+			5 => {
+				let len = get::<usize>(bytes)?;
+				// do not trust the length for preallocation
+				let mut keys = Vec::with_capacity(len.min(bytes.len()));
+				for _ in 0..len {
+					keys.push(get_str(bytes)?);
+				}
+				Self::Keys(keys)
+			}
+			6 => {
+				let complete = get(bytes)?;
+				let cursor = get(bytes)?;
+				let len = get::<usize>(bytes)?;
+				// do not trust the length for preallocation
+				let mut changes = Vec::with_capacity(len.min(bytes.len()));
+				for _ in 0..len {
+					changes.push(get_change(bytes)?);
+				}
+				Self::Changes(Changes {
+					changes,
+					complete,
+					cursor,
+				})
+			}
+			_ => return Err(malformed()),
+		};
+		if bytes.is_empty() { Ok(response) } else { Err(malformed()) }
+	}
+}
+// endregion:	--- messages
+
+// region:		--- serve
+/// Answers a single `request` using `board`.
+#[must_use]
+pub fn handle(board: &Databoard, registry: &Registry, request: Request) -> Response {
+	let value = |(type_name, bytes)| Response::Value(Some(Value { type_name, bytes }));
+	let result = match request {
+		Request::ContainsKey { key } => Ok(Response::Bool(board.contains_key(&key))),
+		Request::Delete { key, type_name } => registry
+			.delete(board, &key, &type_name)
+			.map(|bytes| value((type_name, bytes))),
+		Request::Get { key } => registry.get(board, &key).map(value),
+		// `SequenceId` is already a `u64` with feature `u64-sequence-id`
+		#[allow(clippy::unnecessary_cast)]
+		Request::SequenceId { key } => board
+			.sequence_id(&key)
+			.map(|id| Response::SequenceId(id as u64)),
+		Request::Set { key, value } => registry
+			.set(board, &key, &value.type_name, &value.bytes)
+			.map(|old| {
+				Response::Value(old.map(|bytes| Value {
+					type_name: value.type_name,
+					bytes,
+				}))
+			}),
+		Request::List => Ok(Response::Keys(board.keys())),
+		Request::Subscribe { cursor } => Ok(Response::Changes(board.changes_since(cursor))),
+	};
+	result.unwrap_or_else(Response::Error)
+}
+
+/// Serves requests arriving on `stream` using `board`, until the stream is closed.
+/// # Errors
+/// - [`Error::Remote`] if the communication fails.
+pub fn serve(board: &Databoard, registry: &Registry, mut stream: impl Read + Write) -> Result<()> {
+	while let Some(payload) = read_frame(&mut stream)? {
+		let response = match Request::decode(&payload) {
+			Ok(request) => handle(board, registry, request),
+			Err(error) => Response::Error(error),
+		};
+		write_frame(&mut stream, &response.encode())?;
+	}
+	Ok(())
+}
+// endregion:	--- serve
+
+// region:		--- RemoteDataboard
+/// Access to a [`Databoard`] served by [`serve`] on the other end of a byte `stream`.
+///
+/// Values are transferred serialized, so only types registered in the [`Registry`] can be accessed.
+/// The keys are resolved by the remote [`Databoard`].
+pub struct RemoteDataboard<S: Read + Write> {
+	stream: S,
+	registry: Arc<Registry>,
+}
+
+impl<S: Read + Write> core::fmt::Debug for RemoteDataboard<S> {
+	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+		write!(f, "RemoteDataboard {{ {:?} }}", &self.registry)
+	}
+}
+
+impl<S: Read + Write> RemoteDataboard<S> {
+	/// Creates a [`RemoteDataboard`] communicating over `stream`.
+	pub const fn new(stream: S, registry: Arc<Registry>) -> Self {
+		Self { stream, registry }
+	}
+
+	/// Sends `request` and returns the response, converting [`Response::Error`] into an error.
+	fn request(&mut self, request: &Request) -> Result<Response> {
+		write_frame(&mut self.stream, &request.encode())?;
+		let payload = read_frame(&mut self.stream)?.ok_or_else(|| Error::Remote {
+			message: "connection closed".into(),
+		})?;
+		match Response::decode(&payload)? {
+			Response::Error(error) => Err(error),
+			response => Ok(response),
+		}
+	}
+
+	/// Returns the name of the registered type `T`.
+	fn type_name<T: Any>(&self, key: &str) -> Result<ConstString> {
+		self.registry
+			.name_of::<T>()
+			.cloned()
+			.ok_or_else(|| Error::Unregistered { key: key.into() })
+	}
+
+	/// Deserializes a transferred value of type `T`.
+	fn value<T: Serial + Any>(&self, key: &str, value: &Value) -> Result<T> {
+		if value.type_name != self.type_name::<T>(key)? {
+			return Err(Error::wrong_type::<T>(key, None));
+		}
+		deserialize(key, &value.bytes)
+	}
+
+	/// Returns `true` if a certain `key` is available, otherwise `false`.
+	/// # Errors
+	/// - [`Error::Remote`] if the communication fails.
+	pub fn contains_key(&mut self, key: &str) -> Result<bool> {
+		match self.request(&Request::ContainsKey { key: key.into() })? {
+			Response::Bool(value) => Ok(value),
+			_ => Err(malformed()),
+		}
+	}
+
+	/// Returns the value of type `T` stored under `key` and deletes it from the remote [`Databoard`].
+	/// # Errors
+	/// - [`Error::Remote`]       if the communication fails.
+	/// - [`Error::Unregistered`] if the type `T` or the type of the entry is not registered.
+	/// - [`Error::WrongType`]    if the entry has not the expected type `T`.
+	/// - errors of [`DataboardInner::delete`](crate::databoard::DataboardInner::delete)
+	pub fn delete<T: Serial + Any>(&mut self, key: &str) -> Result<T> {
+		// the type is checked by the remote side, so a mismatching entry is not deleted
+		let request = Request::Delete {
+			key: key.into(),
+			type_name: self.type_name::<T>(key)?,
+		};
+		match self.request(&request)? {
+			Response::Value(Some(value)) => self.value(key, &value),
+			_ => Err(malformed()),
+		}
+	}
+
+	/// Returns a copy of the value of type `T` stored under `key`.
+	/// # Errors
+	/// - [`Error::Remote`]       if the communication fails.
+	/// - [`Error::Unregistered`] if the type `T` or the type of the entry is not registered.
+	/// - [`Error::WrongType`]    if the entry has not the expected type `T`.
+	/// - errors of [`DataboardInner::get`](crate::databoard::DataboardInner::get)
+	pub fn get<T: Serial + Any>(&mut self, key: &str) -> Result<T> {
+		match self.request(&Request::Get { key: key.into() })? {
+			Response::Value(Some(value)) => self.value(key, &value),
+			_ => Err(malformed()),
+		}
+	}
+
+	/// Returns a copy of the serialized value stored under `key` together with the name of its type.
+	/// # Errors
+	/// - [`Error::Remote`]       if the communication fails.
+	/// - [`Error::Unregistered`] if the type of the entry is not registered.
+	/// - errors of [`DataboardInner::get`](crate::databoard::DataboardInner::get)
+	pub fn get_value(&mut self, key: &str) -> Result<Value> {
+		match self.request(&Request::Get { key: key.into() })? {
+			Response::Value(Some(value)) => Ok(value),
+			_ => Err(malformed()),
+		}
+	}
+
+	/// Returns the sorted keys of the entries stored in the remote [`Databoard`] itself.
+	/// # Errors
+	/// - [`Error::Remote`] if the communication fails.
+	pub fn list(&mut self) -> Result<Vec<ConstString>> {
+		match self.request(&Request::List)? {
+			Response::Keys(keys) => Ok(keys),
+			_ => Err(malformed()),
+		}
+	}
+
+	/// Returns the [`Registry`] of transferable types.
+	#[must_use]
+	pub const fn registry(&self) -> &Arc<Registry> {
+		&self.registry
+	}
+
+	/// Returns the sequence id of an entry.
+	/// # Errors
+	/// - [`Error::Remote`] if the communication fails.
+	/// - errors of [`DataboardInner::sequence_id`](crate::databoard::DataboardInner::sequence_id)
+	pub fn sequence_id(&mut self, key: &str) -> Result<u64> {
+		match self.request(&Request::SequenceId { key: key.into() })? {
+			Response::SequenceId(value) => Ok(value),
+			_ => Err(malformed()),
+		}
+	}
+
+	/// Stores the value of type `T` under `key` and returns an eventually existing value of type `T`.
+	/// # Errors
+	/// - [`Error::Remote`]       if the communication fails.
+	/// - [`Error::Unregistered`] if the type `T` is not registered.
+	/// - errors of [`DataboardInner::set`](crate::databoard::DataboardInner::set)
+	pub fn set<T: Serial + Any>(&mut self, key: &str, value: &T) -> Result<Option<T>> {
+		let value = Value {
+			type_name: self.type_name::<T>(key)?,
+			bytes: serialize(value),
+		};
+		match self.request(&Request::Set { key: key.into(), value })? {
+			Response::Value(Some(value)) => self.value(key, &value).map(Some),
+			Response::Value(None) => Ok(None),
+			_ => Err(malformed()),
+		}
+	}
+
+	/// Returns the changes recorded in the journal of the remote [`Databoard`] since `cursor`.
+	/// See [`DataboardInner::changes_since`](crate::databoard::DataboardInner::changes_since).
+	/// # Errors
+	/// - [`Error::Remote`] if the communication fails.
+	pub fn subscribe(&mut self, cursor: u64) -> Result<Changes> {
+		match self.request(&Request::Subscribe { cursor })? {
+			Response::Changes(changes) => Ok(changes),
+			_ => Err(malformed()),
+		}
+	}
+}
+// endregion:	--- RemoteDataboard
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	// check, that the auto traits are available
+	const fn is_normal<T: Sized + Send + Sync>() {}
+
+	#[test]
+	const fn normal_types() {
+		is_normal::<Request>();
+		is_normal::<Response>();
+		is_normal::<Value>();
+		is_normal::<RemoteDataboard<std::net::TcpStream>>();
+	}
+
+	#[test]
+	fn messages() {
+		let requests = [
+			Request::ContainsKey { key: "a".into() },
+			Request::Delete {
+				key: "b".into(),
+				type_name: "i32".into(),
+			},
+			Request::Get { key: "c".into() },
+			Request::SequenceId { key: "d".into() },
+			Request::List,
+			Request::Subscribe { cursor: 42 },
+			Request::Set {
+				key: "e".into(),
+				value: Value {
+					type_name: "i32".into(),
+					bytes: serialize(&42_i32),
+				},
+			},
+		];
+		for request in requests {
+			assert_eq!(Request::decode(&request.encode()).ok(), Some(request));
+		}
+		assert!(Request::decode(&[8]).is_err());
+		assert!(Request::decode(&[]).is_err());
+
+		assert!(matches!(
+			Response::decode(&Response::Error(Error::not_found("a")).encode()),
+			Ok(Response::Error(Error::NotFound { key, .. })) if key.as_ref() == "a"
+		));
+		assert!(matches!(
+			Response::decode(&Response::Value(None).encode()),
+			Ok(Response::Value(None))
+		));
+		assert!(matches!(
+			Response::decode(&Response::Keys(alloc::vec!["a".into(), "b".into()]).encode()),
+			Ok(Response::Keys(keys)) if keys.len() == 2 && keys[1].as_ref() == "b"
+		));
+		let changes = Changes {
+			changes: alloc::vec![
+				Change {
+					key: "a".into(),
+					operation: Operation::Modify,
+					sequence_id: 2,
+				},
+				Change {
+					key: "b".into(),
+					operation: Operation::Delete,
+					sequence_id: 0,
+				},
+			],
+			complete: true,
+			cursor: 7,
+		};
+		assert!(matches!(
+			Response::decode(&Response::Changes(changes.clone()).encode()),
+			Ok(Response::Changes(result)) if result.changes == changes.changes && result.complete && result.cursor == 7
+		));
+	}
+}
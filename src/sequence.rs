@@ -0,0 +1,83 @@
+// Copyright © 2025 Stephan Kunz
+//! Sequence ids counting the changes of entries and [`Databoard`](crate::databoard::Databoard)s.
+
+#[cfg(feature = "u64-sequence-id")]
+use portable_atomic::AtomicU64 as AtomicSequenceId;
+#[cfg(not(feature = "u64-sequence-id"))]
+use portable_atomic::AtomicUsize as AtomicSequenceId;
+use portable_atomic::Ordering;
+
+/// The type of sequence ids.
+///
+/// It is a [`usize`] by default, with the feature `u64-sequence-id` it is a [`u64`],
+/// which wraps around less often on platforms with a small [`usize`].
+/// On platforms without native 64 bit atomics the [`u64`] counters are provided by the
+/// `fallback` feature of `portable-atomic`, which this crate enables.
+#[cfg(not(feature = "u64-sequence-id"))]
+pub type SequenceId = usize;
+/// The type of sequence ids.
+///
+/// It is a [`usize`] by default, with the feature `u64-sequence-id` it is a [`u64`],
+/// which wraps around less often on platforms with a small [`usize`].
+/// On platforms without native 64 bit atomics the [`u64`] counters are provided by the
+/// `fallback` feature of `portable-atomic`, which this crate enables.
+#[cfg(feature = "u64-sequence-id")]
+pub type SequenceId = u64;
+
+/// Returns the sequence id following `id`.
+/// It wraps around to '1' after reaching [`SequenceId::MAX`], so '0' can be used as "never changed".
+#[must_use]
+pub const fn next(id: SequenceId) -> SequenceId {
+	if id < SequenceId::MAX { id + 1 } else { 1 }
+}
+
+/// A thread safe sequence id, which is increased using [`next`].
+#[derive(Debug, Default)]
+pub struct SequenceCounter(AtomicSequenceId);
+
+impl SequenceCounter {
+	/// Creates a [`SequenceCounter`] starting at `id`.
+	#[must_use]
+	pub const fn new(id: SequenceId) -> Self {
+		Self(AtomicSequenceId::new(id))
+	}
+
+	/// Returns the current sequence id.
+	#[must_use]
+	pub fn get(&self) -> SequenceId {
+		self.0.load(Ordering::Acquire)
+	}
+
+	/// Increases the sequence id.
+	pub fn increment(&self) {
+		let _ = self
+			.0
+			.fetch_update(Ordering::AcqRel, Ordering::Acquire, |id| Some(next(id)));
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	// check, that the auto traits are available
+	const fn is_normal<T: Sized + Send + Sync>() {}
+
+	#[test]
+	const fn normal_types() {
+		is_normal::<SequenceCounter>();
+	}
+
+	#[test]
+	fn wrap_around() {
+		assert_eq!(next(0), 1);
+		assert_eq!(next(1), 2);
+		assert_eq!(next(SequenceId::MAX), 1);
+
+		let counter = SequenceCounter::new(SequenceId::MAX - 1);
+		counter.increment();
+		assert_eq!(counter.get(), SequenceId::MAX);
+		counter.increment();
+		assert_eq!(counter.get(), 1);
+	}
+}
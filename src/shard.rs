@@ -0,0 +1,312 @@
+// Copyright © 2025 Stephan Kunz
+//! Implementation of a single [`Shard`] of the [`Database`](crate::database::Database).
+
+use crate::{
+	ConstString, Error,
+	atomic::{AtomicEntry, AtomicValue},
+	entry::{EntryData, EntryPtr, EntryReadGuard, EntryWriteGuard},
+	error::Result,
+	sequence::SequenceId,
+};
+use alloc::{
+	boxed::Box,
+	collections::btree_map::{BTreeMap, Entry},
+	sync::Arc,
+};
+use core::any::Any;
+use spin::RwLock;
+
+/// A single entry in the [`Database`](crate::database::Database).
+pub enum Slot {
+	/// An entry protected by a lock, which can be referenced.
+	Locked(EntryPtr),
+	/// A lock-free entry for an [`AtomicValue`].
+	Atomic(AtomicEntry),
+}
+
+impl Slot {
+	/// Creates a lock protected [`Slot`] holding `value`.
+	pub fn locked<T: Any + Send + Sync>(value: T) -> Self {
+		Self::Locked(Arc::new(RwLock::new(EntryData::new(value))))
+	}
+
+	/// Creates a lock-free [`Slot`] holding `value`.
+	pub fn atomic<T: AtomicValue>(value: T) -> Self {
+		Self::Atomic(AtomicEntry::new(value))
+	}
+
+	/// Returns the sequence id of the entry.
+	#[must_use]
+	pub fn sequence_id(&self) -> SequenceId {
+		match self {
+			Self::Locked(entry) => entry.read().sequence_id,
+			Self::Atomic(entry) => entry.sequence_id(),
+		}
+	}
+
+	/// Returns the sequence id of the entry, or `None` if the entry is write locked by someone else.
+	#[must_use]
+	pub fn try_sequence_id(&self) -> Option<SequenceId> {
+		match self {
+			Self::Locked(entry) => entry.try_read().map(|data| data.sequence_id),
+			Self::Atomic(entry) => Some(entry.sequence_id()),
+		}
+	}
+}
+
+impl core::fmt::Debug for Slot {
+	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+		match self {
+			Self::Locked(entry) => {
+				let data = entry.read();
+				write!(f, "sequence_id: {:?}", data.sequence_id)?;
+				let value = data.data().as_ref();
+				write!(f, ", value: {value:?}")
+			}
+			Self::Atomic(entry) => {
+				write!(f, "sequence_id: {:?}", entry.sequence_id())?;
+				write!(f, ", value: {entry:?}")
+			}
+		}
+	}
+}
+
+/// Holds a part of the [`Database`](crate::database::Database) data.
+#[derive(Default)]
+pub struct Shard {
+	storage: BTreeMap<ConstString, Slot>,
+}
+
+impl Shard {
+	/// Returns `true` if a certain `key` is available, otherwise `false`.
+	#[must_use]
+	pub fn contains_key(&self, key: &str) -> bool {
+		self.storage.contains_key(key)
+	}
+
+	/// Removes all entries from storage and returns them.
+	/// Outstanding references to entries stay valid.
+	pub fn drain(&mut self) -> BTreeMap<ConstString, Slot> {
+		core::mem::take(&mut self.storage)
+	}
+
+	/// Returns the value of type `T` stored under `key` and deletes it from storage.
+	///
+	/// An entry can only be deleted if nobody else holds a reference to it,
+	/// e.g. an [`EntryPtr`], an [`EntryReadGuard`] or an [`EntryWriteGuard`].
+	/// Otherwise the entry is left untouched and [`Error::InUse`] is returned.
+	/// # Errors
+	/// - [`Error::InUse`]     if the entry is still referenced elsewhere.
+	/// - [`Error::NotFound`]  if `key` is not contained.
+	/// - [`Error::WrongType`] if the entry has not the expected type `T`.
+	pub fn delete<T: Any + Send + Sync>(&mut self, key: &str) -> Result<T> {
+		// check type, entry can not be locked as nobody else holds a reference
+		let (is_t, stored) = match self.check_unused(key)? {
+			Slot::Locked(entry) => {
+				let data = entry.read();
+				(data.data.is::<T>(), data.type_name)
+			}
+			Slot::Atomic(entry) => (entry.is::<T>(), entry.type_name()),
+		};
+		if !is_t {
+			return Err(Error::wrong_type::<T>(key, Some(stored)));
+		}
+
+		match self.storage.remove(key) {
+			Some(Slot::Locked(old)) => {
+				if let Some(entry) = Arc::into_inner(old)
+					&& let Ok(t) = entry.into_inner().data.downcast::<T>()
+				{
+					return Ok(*t);
+				}
+			}
+			Some(Slot::Atomic(entry)) => {
+				if let Some(t) = entry.load::<T>() {
+					return Ok(t);
+				}
+			}
+			None => {}
+		}
+
+		// We should never reach this!
+		Err(Error::Unreachable(file!().into(), line!()))
+	}
+
+	/// Returns the slot stored under `key`, if nobody else holds a reference to it.
+	/// Callers must hold `self` borrowed mutably, so that nobody can obtain a new reference in the meantime.
+	fn check_unused(&self, key: &str) -> Result<&Slot> {
+		let slot = self
+			.storage
+			.get(key)
+			.ok_or_else(|| Error::not_found(key))?;
+		if let Slot::Locked(entry) = slot
+			&& Arc::strong_count(entry) > 1
+		{
+			return Err(Error::InUse { key: key.into() });
+		}
+		Ok(slot)
+	}
+
+	/// Returns a clone of the [`EntryPtr`]
+	/// # Errors
+	/// - [`Error::LockFree`] if the entry is stored lock-free.
+	/// - [`Error::NotFound`] if `key` is not contained.
+	pub fn entry(&self, key: &str) -> Result<EntryPtr> {
+		self.locked(key).cloned()
+	}
+
+	/// Returns the [`Slot`] stored under `key` together with the unused `value`.
+	/// If `key` is not contained, the [`Slot`] created from `value` by `create` is stored and `None` is returned.
+	pub fn get_or_create<T>(
+		&mut self,
+		key: impl Into<ConstString>,
+		value: T,
+		create: impl FnOnce(T) -> Slot,
+	) -> Option<(&Slot, T)> {
+		match self.storage.entry(key.into()) {
+			Entry::Occupied(entry) => Some((entry.into_mut(), value)),
+			Entry::Vacant(entry) => {
+				entry.insert(create(value));
+				None
+			}
+		}
+	}
+
+	/// Stores an already existing [`Slot`] under `key`.
+	/// # Errors
+	/// - [`Error::AlreadyExists`] if `key` already exists.
+	pub fn insert_entry(&mut self, key: impl Into<ConstString>, slot: Slot) -> Result<()> {
+		let key = key.into();
+		if self.storage.contains_key(&key) {
+			return Err(Error::AlreadyExists { key });
+		}
+
+		if self.storage.insert(key, slot).is_some() {
+			return Err(Error::Unreachable(file!().into(), line!()));
+		}
+		Ok(())
+	}
+
+	/// Returns an iterator over all keys and their [`Slot`]s.
+	pub fn iter(&self) -> impl Iterator<Item = (&ConstString, &Slot)> {
+		self.storage.iter()
+	}
+
+	/// Returns `true` if the shard contains no entries, otherwise `false`.
+	#[must_use]
+	pub fn is_empty(&self) -> bool {
+		self.storage.is_empty()
+	}
+
+	/// Returns the number of entries in the shard.
+	#[must_use]
+	pub fn len(&self) -> usize {
+		self.storage.len()
+	}
+
+	/// Returns the [`EntryPtr`] of a lock protected entry.
+	fn locked(&self, key: &str) -> Result<&EntryPtr> {
+		match self.storage.get(key) {
+			Some(Slot::Locked(entry)) => Ok(entry),
+			Some(Slot::Atomic(_)) => Err(Error::LockFree { key: key.into() }),
+			None => Err(Error::not_found(key)),
+		}
+	}
+
+	/// Returns the type erased value stored under `key` and deletes it from storage.
+	///
+	/// An entry can only be removed if nobody else holds a reference to it,
+	/// e.g. an [`EntryPtr`], an [`EntryReadGuard`] or an [`EntryWriteGuard`].
+	/// Otherwise the entry is left untouched and [`Error::InUse`] is returned.
+	/// # Errors
+	/// - [`Error::InUse`]     if the entry is still referenced elsewhere.
+	/// - [`Error::NotFound`]  if `key` is not contained.
+	pub fn remove(&mut self, key: &str) -> Result<Box<dyn Any + Send + Sync>> {
+		self.check_unused(key)?;
+		match self.storage.remove(key) {
+			Some(Slot::Locked(old)) => {
+				if let Some(entry) = Arc::into_inner(old) {
+					return Ok(entry.into_inner().data);
+				}
+			}
+			Some(Slot::Atomic(entry)) => return Ok(entry.boxed()),
+			None => {}
+		}
+
+		// We should never reach this!
+		Err(Error::Unreachable(file!().into(), line!()))
+	}
+
+	/// Removes the [`Slot`] stored under `key` from storage and returns it.
+	/// The entry itself is left untouched, so outstanding references stay valid.
+	/// # Errors
+	/// - [`Error::NotFound`] if `key` is not contained.
+	pub fn remove_entry(&mut self, key: &str) -> Result<Slot> {
+		self.storage
+			.remove(key)
+			.ok_or_else(|| Error::not_found(key))
+	}
+
+	/// Stores `slot` under `key`, replacing an existing entry, if nobody else holds a reference to it.
+	/// Returns `true` if an entry was replaced.
+	/// # Errors
+	/// - [`Error::InUse`] if the existing entry is still referenced elsewhere.
+	pub fn replace_entry(&mut self, key: ConstString, slot: Slot) -> Result<bool> {
+		let replaced = match self.check_unused(&key) {
+			Ok(_) => true,
+			Err(Error::NotFound { .. }) => false,
+			Err(error) => return Err(error),
+		};
+		self.storage.insert(key, slot);
+		Ok(replaced)
+	}
+
+	/// Returns the [`Slot`] stored under `key`, if there is one.
+	#[must_use]
+	pub fn slot(&self, key: &str) -> Option<&Slot> {
+		self.storage.get(key)
+	}
+
+	/// Returns a read/write guard to the `T` of the `entry` stored under `key`.
+	/// The entry is locked for read & write while this reference is held.
+	/// Multiple changes during holding the reference are counted as a single change,
+	/// so `sequence_id()`will only increase by 1.
+	///
+	/// You need to drop the received [`EntryWriteGuard`] before using `delete`, `read`, `update` or `sequence_id`.
+	/// # Errors
+	/// - [`Error::LockFree`]  if the entry is stored lock-free.
+	/// - [`Error::IsLocked`]  if the entry is locked by someone else.
+	/// - [`Error::NotFound`]  if `key` is not contained.
+	/// - [`Error::WrongType`] if the entry has not the expected type `T`.
+	pub fn try_get_mut_ref<T: Any + Send + Sync>(&self, key: &str) -> Result<EntryWriteGuard<T>> {
+		let entry = self.locked(key)?;
+		EntryWriteGuard::try_new(key, entry)
+	}
+
+	/// Returns a read guard to the `T` of the `entry` stored under `key`.
+	/// The entry is locked for write while this reference is held.
+	///
+	/// You need to drop the received [`EntryReadGuard`] before using `delete`, or `update`.
+	/// # Errors
+	/// - [`Error::LockFree`]  if the entry is stored lock-free.
+	/// - [`Error::IsLocked`]  if the entry is write locked by someone else.
+	/// - [`Error::NotFound`]  if `key` is not contained.
+	/// - [`Error::WrongType`] if the entry has not the expected type `T`.
+	pub fn try_get_ref<T: Any + Send + Sync>(&self, key: &str) -> Result<EntryReadGuard<T>> {
+		let entry = self.locked(key)?;
+		EntryReadGuard::try_new(key, entry)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	// check, that the auto traits are available
+	const fn is_normal<T: Sized + Send + Sync>() {}
+
+	#[test]
+	const fn normal_types() {
+		is_normal::<Shard>();
+	}
+}
@@ -0,0 +1,127 @@
+// Copyright © 2025 Stephan Kunz
+//! Implements the read-only [`DataboardView`].
+
+use crate::{Databoard, backoff::Backoff, entry::EntryReadGuard, error::Result, sequence::SequenceId};
+use core::any::Any;
+
+#[cfg(doc)]
+use crate::Error;
+
+/// A read-only view of a [`Databoard`].
+///
+/// The view resolves keys with the same remapping rules as the viewed [`Databoard`],
+/// but does not offer any method to change entries, so it can be handed to code which must not write.
+#[derive(Clone)]
+pub struct DataboardView(Databoard);
+
+impl core::fmt::Debug for DataboardView {
+	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+		write!(f, "DataboardView {{ {:?} }}", &self.0)
+	}
+}
+
+impl From<Databoard> for DataboardView {
+	fn from(board: Databoard) -> Self {
+		Self(board)
+	}
+}
+
+impl From<&Databoard> for DataboardView {
+	fn from(board: &Databoard) -> Self {
+		Self(board.clone())
+	}
+}
+
+impl DataboardView {
+	/// Returns `true` if a certain `key` is available, otherwise `false`.
+	#[must_use]
+	pub fn contains_key(&self, key: &str) -> bool {
+		self.0.contains_key(key)
+	}
+
+	/// Returns a result of `true` if a certain `key` is available, otherwise a result of `false`.
+	/// # Errors
+	/// - [`Error::NoParent`]  if `key` is remapped to a parent without having a parent.
+	/// - [`Error::WrongType`] if the entry has not the expected type `T`.
+	pub fn contains<T: Any + Send + Sync>(&self, key: &str) -> Result<bool> {
+		self.0.contains::<T>(key)
+	}
+
+	/// Returns a copy of the value of type `T` stored under `key`.
+	/// # Errors
+	/// - [`Error::Assignment`] if the remapping contains an assignment of a `str` value.
+	/// - [`Error::NoParent`]   if `key` is remapped to a parent without having a parent.
+	/// - [`Error::NotFound`]   if `key` is not contained.
+	/// - [`Error::WrongType`]  if the entry has not the expected type `T`.
+	pub fn get<T: Any + Clone + Send + Sync>(&self, key: &str) -> Result<T> {
+		self.0.get(key)
+	}
+
+	/// Returns a read guard to the `T` of the `entry` stored under `key`.
+	/// The entry is locked for write while this reference is held.
+	/// # Errors
+	/// - [`Error::Assignment`] if the remapping contains an assignment of a `str` value.
+	/// - [`Error::LockFree`]   if the entry is stored lock-free.
+	/// - [`Error::NoParent`]   if `key` is remapped to a parent without having a parent.
+	/// - [`Error::NotFound`]   if `key` is not contained.
+	/// - [`Error::WrongType`]  if the entry has not the expected type `T`.
+	pub fn get_ref<T: Any + Send + Sync>(&self, key: &str) -> Result<EntryReadGuard<T>> {
+		self.0.get_ref(key)
+	}
+
+	/// Returns a read guard to the `T` of the `entry` stored under `key`.
+	/// If the entry is locked by someone else, further attempts are made as long as the `backoff` allows.
+	/// # Errors
+	/// - [`Error::Assignment`] if the remapping contains an assignment of a `str` value.
+	/// - [`Error::IsLocked`]   if the entry is still locked by someone else when `backoff` gives up.
+	/// - [`Error::LockFree`]   if the entry is stored lock-free.
+	/// - [`Error::NoParent`]   if `key` is remapped to a parent without having a parent.
+	/// - [`Error::NotFound`]   if `key` is not contained.
+	/// - [`Error::WrongType`]  if the entry has not the expected type `T`.
+	pub fn get_ref_timeout<T: Any + Send + Sync>(&self, key: &str, backoff: impl Backoff) -> Result<EntryReadGuard<T>> {
+		self.0.get_ref_timeout(key, backoff)
+	}
+
+	/// Returns the sequence id of an entry.
+	/// # Errors
+	/// - [`Error::Assignment`] if the remapping contains an assignment of a `str` value.
+	/// - [`Error::NoParent`]   if `key` is remapped to a parent without having a parent.
+	/// - [`Error::NotFound`]   if `key` is not contained.
+	pub fn sequence_id(&self, key: &str) -> Result<SequenceId> {
+		self.0.sequence_id(key)
+	}
+
+	/// Returns a read guard to the `T` of the `entry` stored under `key`, without waiting for a lock.
+	/// # Errors
+	/// - [`Error::Assignment`] if the remapping contains an assignment of a `str` value.
+	/// - [`Error::IsLocked`]   if the entry is locked by someone else.
+	/// - [`Error::LockFree`]   if the entry is stored lock-free.
+	/// - [`Error::NoParent`]   if `key` is remapped to a parent without having a parent.
+	/// - [`Error::NotFound`]   if `key` is not contained.
+	/// - [`Error::WrongType`]  if the entry has not the expected type `T`.
+	pub fn try_get_ref<T: Any + Send + Sync>(&self, key: &str) -> Result<EntryReadGuard<T>> {
+		self.0.try_get_ref(key)
+	}
+
+	/// Returns the name of the type stored under `key`.
+	/// # Errors
+	/// - [`Error::Assignment`] if the remapping contains an assignment of a `str` value.
+	/// - [`Error::NoParent`]   if `key` is remapped to a parent without having a parent.
+	/// - [`Error::NotFound`]   if `key` is not contained.
+	pub fn type_name_of(&self, key: &str) -> Result<&'static str> {
+		self.0.type_name_of(key)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	// check, that the auto traits are available
+	const fn is_normal<T: Sized + Send + Sync>() {}
+
+	#[test]
+	const fn normal_types() {
+		is_normal::<DataboardView>();
+	}
+}
@@ -0,0 +1,94 @@
+// Copyright © 2025 Stephan Kunz
+//! Integration tests for lock-free entries of a [`Databoard`].
+
+#![allow(unused)]
+#![allow(missing_docs)]
+#![allow(clippy::unwrap_used)]
+#![allow(clippy::cognitive_complexity)]
+
+use databoard::{Databoard, Error};
+
+#[test]
+fn lock_free_entries() {
+	let root = Databoard::new();
+	let level1 = Databoard::with_parent(root.clone());
+
+	assert_eq!(level1.set_atomic("counter", 0_u32).unwrap(), None);
+	assert_eq!(level1.set_atomic("flag", false).unwrap(), None);
+	assert_eq!(level1.set_atomic("_local", -1.5_f64).unwrap(), None);
+	assert!(root.contains_key("counter"));
+	assert!(root.contains::<u32>("counter").unwrap());
+	assert!(root.contains::<i32>("counter").is_err());
+	assert!(!root.contains_key("local"));
+
+	// get and set without locking
+	assert_eq!(root.get::<u32>("counter").unwrap(), 0);
+	assert!(root.get::<i32>("counter").is_err());
+	assert_eq!(root.set("counter", 1_u32).unwrap(), Some(0));
+	assert!(root.set("counter", 1_i32).is_err());
+	assert_eq!(level1.set_atomic("counter", 2_u32).unwrap(), Some(1));
+	assert_eq!(level1.get::<u32>("counter").unwrap(), 2);
+	assert_eq!(level1.sequence_id("counter").unwrap(), 3);
+	assert_eq!(level1.set("flag", true).unwrap(), Some(false));
+	assert!(level1.get::<bool>("flag").unwrap());
+	assert_eq!(level1.set("_local", 2.5_f64).unwrap(), Some(-1.5));
+	assert!((level1.get::<f64>("_local").unwrap() - 2.5).abs() < f64::EPSILON);
+	assert_eq!(level1.sequence_id("_local").unwrap(), 2);
+
+	// references are not possible
+	assert!(matches!(root.entry("counter"), Err(Error::LockFree { .. })));
+	assert!(matches!(root.get_ref::<u32>("counter"), Err(Error::LockFree { .. })));
+	assert!(matches!(root.get_mut_ref::<u32>("counter"), Err(Error::LockFree { .. })));
+	assert!(matches!(
+		root.get_upgradable_ref::<u32>("counter"),
+		Err(Error::LockFree { .. })
+	));
+	assert!(matches!(root.try_get_ref::<u32>("counter"), Err(Error::LockFree { .. })));
+	assert!(matches!(root.try_get_mut_ref::<u32>("counter"), Err(Error::LockFree { .. })));
+
+	// existing locked entries stay locked
+	assert_eq!(root.set("locked", 42_i64).unwrap(), None);
+	assert_eq!(root.set_atomic("locked", 24_i64).unwrap(), Some(42));
+	assert!(root.get_ref::<i64>("locked").is_ok());
+
+	// debug output shows the value
+	let string = format!("{:?}", &root);
+	assert!(string.contains("(key: counter, sequence_id: 3, value: 2)"));
+
+	// rename, delete and remove
+	root.rename("counter", "renamed").unwrap();
+	assert_eq!(root.sequence_id("renamed").unwrap(), 3);
+	assert!(root.delete::<i32>("renamed").is_err());
+	assert_eq!(root.delete::<u32>("renamed").unwrap(), 2);
+	assert!(
+		*root
+			.remove("flag")
+			.unwrap()
+			.downcast::<bool>()
+			.unwrap()
+	);
+	assert_eq!(root.len(), 1);
+}
+
+#[test]
+fn lock_free_concurrency() {
+	let databoard = Databoard::new();
+	assert_eq!(databoard.set_atomic("value", 0_u64).unwrap(), None);
+
+	let handles: Vec<_> = (1..=4_u64)
+		.map(|i| {
+			let databoard = databoard.clone();
+			std::thread::spawn(move || {
+				for _ in 0..1000 {
+					databoard.set("value", i).unwrap();
+					let value = databoard.get::<u64>("value").unwrap();
+					assert!(value <= 4);
+				}
+			})
+		})
+		.collect();
+	for handle in handles {
+		handle.join().unwrap();
+	}
+	assert_eq!(databoard.sequence_id("value").unwrap(), 4001);
+}
@@ -0,0 +1,184 @@
+// Copyright © 2025 Stephan Kunz
+//! Integration tests for mirroring a [`Databoard`] over a byte stream.
+
+#![cfg(feature = "std")]
+#![allow(clippy::cognitive_complexity)]
+#![allow(unused)]
+#![allow(missing_docs)]
+#![allow(clippy::unwrap_used)]
+
+use databoard::{Databoard, Error, Mirror, Registry, RemoteDataboard, serve};
+use std::{
+	io::{Read, Write},
+	sync::{
+		Arc,
+		mpsc::{Receiver, Sender, channel},
+	},
+};
+
+/// One end of an in-memory pipe.
+struct End {
+	sender: Sender<Vec<u8>>,
+	receiver: Receiver<Vec<u8>>,
+	buffer: Vec<u8>,
+}
+
+/// Creates both ends of an in-memory pipe.
+fn pipe() -> (End, End) {
+	let (first_sender, first_receiver) = channel();
+	let (second_sender, second_receiver) = channel();
+	(
+		End {
+			sender: first_sender,
+			receiver: second_receiver,
+			buffer: Vec::new(),
+		},
+		End {
+			sender: second_sender,
+			receiver: first_receiver,
+			buffer: Vec::new(),
+		},
+	)
+}
+
+impl Read for End {
+	fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+		if self.buffer.is_empty() {
+			// a closed pipe is the end of the stream
+			match self.receiver.recv() {
+				Ok(bytes) => self.buffer = bytes,
+				Err(_) => return Ok(0),
+			}
+		}
+		let len = buf.len().min(self.buffer.len());
+		buf[..len].copy_from_slice(&self.buffer[..len]);
+		self.buffer.drain(..len);
+		Ok(len)
+	}
+}
+
+impl Write for End {
+	fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+		self.sender
+			.send(buf.to_vec())
+			.map_err(|_| std::io::ErrorKind::BrokenPipe)?;
+		Ok(buf.len())
+	}
+
+	fn flush(&mut self) -> std::io::Result<()> {
+		Ok(())
+	}
+}
+
+fn registry() -> Arc<Registry> {
+	let mut registry = Registry::new();
+	registry.register::<i32>("i32").unwrap();
+	registry.register::<String>("String").unwrap();
+	Arc::new(registry)
+}
+
+fn mirror(robot: &Databoard) -> (Mirror<End>, std::thread::JoinHandle<()>) {
+	let (client, server) = pipe();
+	let board = robot.clone();
+	let handle = std::thread::spawn(move || serve(&board, &registry(), server).unwrap());
+	let remote = RemoteDataboard::new(client, registry());
+	(Mirror::new(remote, Databoard::new()), handle)
+}
+
+#[test]
+fn list_get_set() {
+	let robot = Databoard::new();
+	robot.set("speed", 42_i32).unwrap();
+	robot.set("mode", String::from("idle")).unwrap();
+	robot.set("unregistered", 1_u8).unwrap();
+	let (mut mirror, handle) = mirror(&robot);
+	let remote = mirror.remote();
+
+	let keys: Vec<String> = remote
+		.list()
+		.unwrap()
+		.iter()
+		.map(ToString::to_string)
+		.collect();
+	assert_eq!(keys, ["mode", "speed", "unregistered"]);
+	assert_eq!(remote.get::<i32>("speed").unwrap(), 42);
+	assert_eq!(remote.set("speed", &43_i32).unwrap(), Some(42));
+	assert_eq!(robot.get::<i32>("speed").unwrap(), 43);
+	assert_eq!(remote.sequence_id("speed").unwrap(), 2);
+	assert!(matches!(remote.get::<u8>("unregistered"), Err(Error::Unregistered { .. })));
+
+	drop(mirror);
+	handle.join().unwrap();
+}
+
+#[test]
+fn subscribe_and_mirror() {
+	let robot = Databoard::new();
+	robot.set_journal_capacity(4);
+	robot.set("speed", 42_i32).unwrap();
+	robot.set("mode", String::from("idle")).unwrap();
+	robot.set("unregistered", 1_u8).unwrap();
+	let (mut mirror, handle) = mirror(&robot);
+	mirror.board().set("stale", 0_i32).unwrap();
+
+	// the first synchronization transfers all entries of registered types
+	assert_eq!(mirror.sync().unwrap(), 2);
+	let board = mirror.board().clone();
+	assert_eq!(board.get::<i32>("speed").unwrap(), 42);
+	assert_eq!(board.get::<String>("mode").unwrap(), "idle");
+	assert!(!board.contains_key("unregistered"));
+	assert!(!board.contains_key("stale"));
+
+	// nothing changed
+	assert_eq!(mirror.sync().unwrap(), 0);
+
+	// changes are transferred once per entry
+	robot.set("speed", 43_i32).unwrap();
+	*robot.get_mut_ref::<i32>("speed").unwrap() += 1;
+	robot.delete::<String>("mode").unwrap();
+	robot.set("mode", 7_i32).unwrap();
+	let changes = mirror.remote().subscribe(0).unwrap();
+	assert!(!changes.complete);
+	assert_eq!(changes.changes.len(), 4);
+	assert_eq!(mirror.sync().unwrap(), 2);
+	assert_eq!(board.get::<i32>("speed").unwrap(), 44);
+	assert_eq!(board.get::<i32>("mode").unwrap(), 7);
+
+	// changes poked into the remote board
+	mirror
+		.remote()
+		.set("target", &String::from("dock"))
+		.unwrap();
+	assert_eq!(robot.get::<String>("target").unwrap(), "dock");
+	mirror.remote().delete::<i32>("speed").unwrap();
+	assert_eq!(mirror.sync().unwrap(), 2);
+	assert_eq!(board.get::<String>("target").unwrap(), "dock");
+	assert!(!board.contains_key("speed"));
+
+	// too many changes to be kept in the journal result in a resynchronization
+	for i in 0..10 {
+		robot.set("speed", i).unwrap();
+	}
+	robot.delete::<i32>("mode").unwrap();
+	assert_eq!(mirror.sync().unwrap(), 2);
+	assert_eq!(board.get::<i32>("speed").unwrap(), 9);
+	assert!(!board.contains_key("mode"));
+
+	drop(mirror);
+	handle.join().unwrap();
+}
+
+#[test]
+fn without_journal() {
+	let robot = Databoard::new();
+	robot.set("speed", 42_i32).unwrap();
+	let (mut mirror, handle) = mirror(&robot);
+
+	assert_eq!(mirror.sync().unwrap(), 1);
+	robot.set("speed", 43_i32).unwrap();
+	assert_eq!(mirror.sync().unwrap(), 1);
+	assert_eq!(mirror.board().get::<i32>("speed").unwrap(), 43);
+
+	drop(mirror);
+	handle.join().unwrap();
+}
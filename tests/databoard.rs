@@ -6,7 +6,15 @@
 #![allow(missing_docs)]
 #![allow(clippy::unwrap_used)]
 
-use databoard::{Databoard, Remappings};
+use core::time::Duration;
+use databoard::{
+	Change, CloneRegistry, Databoard, DataboardView, Defaults, EntryReadGuard, EntryWriteGuard, Error, MergePolicy,
+	Operation, Remappings, Retries,
+};
+use std::sync::{Arc, Barrier};
+
+#[cfg(feature = "std")]
+use databoard::Timeout;
 
 #[test]
 fn standalone() {
@@ -351,8 +359,8 @@ fn referencing() {
 	// concurrent read or write should not be possible
 	assert!(databoard.try_get_ref::<i32>("test").is_err());
 	assert!(databoard.try_get_mut_ref::<i32>("test").is_err());
-	// a delete will block
-	// databoard.delete::<i32>("test");
+	// a delete is not possible
+	assert!(databoard.delete::<i32>("test").is_err());
 
 	drop(entry);
 	// multiple changes during holding a reference are counted as 1 change
@@ -362,3 +370,990 @@ fn referencing() {
 	assert_eq!(databoard.delete::<i32>("test").unwrap(), 24);
 	assert!(!databoard.contains_key("test"));
 }
+
+#[test]
+fn hierarchy() {
+	let root = Databoard::new();
+	let auto_level1 = Databoard::with_parent(root.clone());
+	let manual_level1 = Databoard::with(Some(root.clone()), None, false);
+	let level2 = Databoard::with_parent(auto_level1.clone());
+	assert_eq!(root.level(), 0);
+	assert_eq!(manual_level1.level(), 1);
+	assert_eq!(level2.level(), 2);
+	assert_eq!(root.children().len(), 2);
+	assert_eq!(auto_level1.children().len(), 1);
+	assert!(level2.children().is_empty());
+
+	assert_eq!(root.set("test", 40).unwrap(), None);
+	assert_eq!(manual_level1.set("test", 41).unwrap(), None);
+	// auto remapped into root
+	assert_eq!(level2.set("other", 42).unwrap(), None);
+	assert_eq!(root.len(), 2);
+	assert_eq!(manual_level1.len(), 1);
+	assert!(auto_level1.is_empty());
+
+	// collect statistics over the whole hierarchy
+	let mut boards = 0;
+	let mut entries = 0;
+	let mut max_depth = 0;
+	root.visit(|board, depth| {
+		boards += 1;
+		entries += board.len();
+		max_depth = max_depth.max(depth);
+	});
+	assert_eq!(boards, 4);
+	assert_eq!(entries, 3);
+	assert_eq!(max_depth, 2);
+
+	// children are only weakly referenced
+	drop(level2);
+	assert!(auto_level1.children().is_empty());
+	let mut boards = 0;
+	root.visit(|_, _| boards += 1);
+	assert_eq!(boards, 3);
+}
+
+#[test]
+fn rename_and_move() {
+	let root = Databoard::new();
+	let level1 = Databoard::with_parent(root.clone());
+	let other = Databoard::new();
+
+	assert_eq!(root.set("test", 40).unwrap(), None);
+	assert_eq!(root.set("test", 42).unwrap(), Some(40));
+	assert_eq!(root.set("existing", 1).unwrap(), None);
+	assert!(root.rename("unknown", "new").is_err());
+	assert!(root.rename("test", "existing").is_err());
+	assert_eq!(root.sequence_id("test").unwrap(), 2);
+
+	// outstanding references stay valid
+	let entry = root.get_ref::<i32>("test").unwrap();
+	root.rename("test", "renamed").unwrap();
+	assert_eq!(*entry, 42);
+	drop(entry);
+	assert!(!root.contains_key("test"));
+	assert_eq!(root.get::<i32>("renamed").unwrap(), 42);
+	assert_eq!(root.sequence_id("renamed").unwrap(), 2);
+
+	// rename via remapping
+	level1.rename("renamed", "_local").unwrap();
+	assert!(!root.contains_key("renamed"));
+	assert_eq!(level1.get::<i32>("_local").unwrap(), 42);
+	assert_eq!(level1.sequence_id("_local").unwrap(), 2);
+
+	// move to an independent board
+	assert!(
+		level1
+			.move_to("_local", &other, "existing")
+			.is_ok()
+	);
+	assert!(
+		level1
+			.move_to("_local", &other, "existing")
+			.is_err()
+	);
+	assert!(
+		root.move_to("existing", &other, "existing")
+			.is_err()
+	);
+	assert!(root.contains_key("existing"));
+	assert_eq!(other.get::<i32>("existing").unwrap(), 42);
+	assert_eq!(other.sequence_id("existing").unwrap(), 2);
+	assert_eq!(other.set("existing", 24).unwrap(), Some(42));
+	assert_eq!(other.sequence_id("existing").unwrap(), 3);
+	assert_eq!(root.get::<i32>("existing").unwrap(), 1);
+}
+
+#[test]
+fn remove_and_clear() {
+	let root = Databoard::new();
+	let level1 = Databoard::with_parent(root.clone());
+	let level2 = Databoard::with(Some(level1.clone()), None, false);
+
+	assert_eq!(root.set("test", 42).unwrap(), None);
+	assert_eq!(root.set("other", String::from("other")).unwrap(), None);
+	assert_eq!(level2.set("test", 24).unwrap(), None);
+
+	assert!(level1.remove("unknown").is_err());
+	let value = level1.remove("test").unwrap();
+	assert_eq!(*value.downcast::<i32>().unwrap(), 42);
+	assert!(!root.contains_key("test"));
+	let value = level2.remove("@other").unwrap();
+	assert_eq!(*value.downcast::<String>().unwrap(), "other");
+	assert!(root.is_empty());
+
+	assert_eq!(root.set("test", 42).unwrap(), None);
+	level2.clear_local();
+	assert!(level2.is_empty());
+	assert!(!root.is_empty());
+
+	assert_eq!(level2.set("test", 24).unwrap(), None);
+	level1.clear_subtree();
+	assert!(level2.is_empty());
+	assert!(!root.is_empty());
+	root.clear();
+	assert!(root.is_empty());
+}
+
+#[test]
+fn clear() {
+	let root = Databoard::new();
+	let mut remappings = Remappings::default();
+	remappings.add("alias", "{target}").unwrap();
+	remappings.add("constant", "42").unwrap();
+	let board = Databoard::with(Some(root.clone()), Some(remappings), false);
+	let sibling = Databoard::with(Some(root.clone()), None, false);
+	let child = Databoard::with(Some(board.clone()), None, false);
+
+	root.set("target", 1_i32).unwrap();
+	root.set("other", 2_i32).unwrap();
+	board.set("local", 3_i32).unwrap();
+	sibling.set("local", 4_i32).unwrap();
+	child.set("local", 5_i32).unwrap();
+
+	// the board itself and the targets of its remappings
+	board.clear();
+	assert!(board.is_empty());
+	assert!(!root.contains_key("target"));
+	assert_eq!(root.get::<i32>("other").unwrap(), 2);
+	assert_eq!(sibling.get::<i32>("local").unwrap(), 4);
+	assert_eq!(child.get::<i32>("local").unwrap(), 5);
+
+	// the board and its descendants
+	board.set("local", 3_i32).unwrap();
+	board.clear_subtree();
+	assert!(board.is_empty());
+	assert!(child.is_empty());
+	assert_eq!(root.len(), 1);
+	assert_eq!(sibling.len(), 1);
+}
+
+#[test]
+fn deleting_referenced_entries() {
+	let databoard = Databoard::new();
+	assert_eq!(databoard.set("test", 42).unwrap(), None);
+
+	// read guard
+	let entry = databoard.get_ref::<i32>("test").unwrap();
+	assert!(matches!(databoard.delete::<i32>("test"), Err(Error::InUse { .. })));
+	assert!(matches!(databoard.remove("test"), Err(Error::InUse { .. })));
+	assert_eq!(*entry, 42);
+	drop(entry);
+
+	// write guard
+	let mut entry = databoard.get_mut_ref::<i32>("test").unwrap();
+	assert!(matches!(databoard.delete::<i32>("test"), Err(Error::InUse { .. })));
+	*entry = 24;
+	drop(entry);
+	assert_eq!(databoard.sequence_id("test").unwrap(), 2);
+
+	// entry pointer
+	let entry = databoard.entry("test").unwrap();
+	assert!(matches!(databoard.delete::<i32>("test"), Err(Error::InUse { .. })));
+	// type is checked after usage
+	assert!(matches!(databoard.delete::<String>("test"), Err(Error::InUse { .. })));
+	drop(entry);
+	assert!(matches!(databoard.delete::<String>("test"), Err(Error::WrongType { .. })));
+
+	// clearing detaches entries in use
+	let entry = databoard.get_ref::<i32>("test").unwrap();
+	databoard.clear();
+	assert!(!databoard.contains_key("test"));
+	assert_eq!(*entry, 24);
+	drop(entry);
+
+	assert_eq!(databoard.set("test", 42).unwrap(), None);
+	assert_eq!(databoard.delete::<i32>("test").unwrap(), 42);
+}
+
+#[test]
+fn try_locking_remapped_entries() {
+	let root = Databoard::new();
+	let level1 = Databoard::with_parent(root.clone());
+	assert_eq!(root.set("test", 42).unwrap(), None);
+
+	let entry = root.get_mut_ref::<i32>("test").unwrap();
+	assert!(matches!(level1.try_get_ref::<i32>("test"), Err(Error::IsLocked { .. })));
+	assert!(matches!(level1.try_get_mut_ref::<i32>("test"), Err(Error::IsLocked { .. })));
+	drop(entry);
+	assert_eq!(*level1.try_get_ref::<i32>("test").unwrap(), 42);
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn locking_with_timeout() {
+	let root = Databoard::new();
+	let level1 = Databoard::with_parent(root.clone());
+	assert_eq!(root.set("test", 42).unwrap(), None);
+
+	let entry = level1.get_mut_ref::<i32>("test").unwrap();
+	assert!(matches!(
+		level1.get_ref_timeout::<i32>("test", Retries::new(10)),
+		Err(Error::IsLocked { .. })
+	));
+	assert!(matches!(
+		level1.get_mut_ref_timeout::<i32>("test", Timeout::new(Duration::from_millis(10))),
+		Err(Error::IsLocked { .. })
+	));
+	let mut attempts = 0;
+	assert!(matches!(
+		level1.get_ref_timeout::<i32>("test", || {
+			attempts += 1;
+			attempts < 5
+		}),
+		Err(Error::IsLocked { .. })
+	));
+	assert_eq!(attempts, 5);
+
+	// other errors are returned immediately
+	assert!(matches!(
+		level1.get_ref_timeout::<i32>("unknown", Retries::new(10)),
+		Err(Error::NotFound { .. })
+	));
+
+	drop(entry);
+
+	// release the lock while waiting
+	let barrier = Arc::new(Barrier::new(2));
+	let handle = {
+		let barrier = barrier.clone();
+		let level1 = level1.clone();
+		std::thread::spawn(move || {
+			let entry = level1.get_mut_ref::<i32>("test").unwrap();
+			barrier.wait();
+			std::thread::sleep(Duration::from_millis(20));
+			drop(entry);
+		})
+	};
+	barrier.wait();
+	assert!(level1.try_get_ref::<i32>("test").is_err());
+	let mut entry = level1
+		.get_mut_ref_timeout::<i32>("test", Timeout::new(Duration::from_secs(10)))
+		.unwrap();
+	*entry += 1;
+	drop(entry);
+	handle.join().unwrap();
+	let entry = level1
+		.get_ref_timeout::<i32>("test", Retries::new(0))
+		.unwrap();
+	assert_eq!(*entry, 43);
+	drop(entry);
+	assert_eq!(root.sequence_id("test").unwrap(), 2);
+}
+
+#[test]
+fn upgradable_referencing() {
+	let root = Databoard::new();
+	let level1 = Databoard::with_parent(root.clone());
+	assert!(level1.get_upgradable_ref::<i32>("test").is_err());
+	assert_eq!(root.set("test", 42).unwrap(), None);
+	assert!(
+		level1
+			.get_upgradable_ref::<String>("test")
+			.is_err()
+	);
+
+	// read only
+	let entry = level1.get_upgradable_ref::<i32>("test").unwrap();
+	assert_eq!(*entry, 42);
+	// concurrent read or write should not be possible
+	assert!(level1.try_get_ref::<i32>("test").is_err());
+	assert!(level1.try_get_mut_ref::<i32>("test").is_err());
+	drop(entry);
+	assert_eq!(root.sequence_id("test").unwrap(), 1);
+
+	// upgrade without modification
+	let entry = level1.get_upgradable_ref::<i32>("test").unwrap();
+	let entry = entry.upgrade();
+	assert_eq!(*entry, 42);
+	drop(entry);
+	assert_eq!(root.sequence_id("test").unwrap(), 1);
+
+	// upgrade with modification
+	let entry = level1.get_upgradable_ref::<i32>("test").unwrap();
+	if *entry == 42 {
+		let mut entry = entry.try_upgrade().ok().unwrap();
+		*entry += 1;
+		*entry += 1;
+		assert!(level1.try_get_ref::<i32>("test").is_err());
+	}
+	assert_eq!(root.sequence_id("test").unwrap(), 2);
+	assert_eq!(root.get::<i32>("test").unwrap(), 44);
+
+	// upgrade is not possible while there are readers
+	let reader = root.get_ref::<i32>("test").unwrap();
+	let entry = level1.get_upgradable_ref::<i32>("test").unwrap();
+	let entry = entry.try_upgrade().err().unwrap();
+	drop(reader);
+	let mut entry = entry.try_upgrade().ok().unwrap();
+	*entry = 24;
+	drop(entry);
+	assert_eq!(root.sequence_id("test").unwrap(), 3);
+	assert_eq!(root.delete::<i32>("test").unwrap(), 24);
+}
+
+#[test]
+fn mapped_referencing() {
+	#[derive(Clone, Debug, Default)]
+	struct Pose {
+		x: i32,
+		y: i32,
+		name: Option<String>,
+	}
+
+	let databoard = Databoard::new();
+	assert!(
+		databoard
+			.set("pose", Pose::default())
+			.unwrap()
+			.is_none()
+	);
+
+	// read access to a field
+	let entry = databoard.get_ref::<Pose>("pose").unwrap();
+	let x = EntryReadGuard::map(entry, |pose| &pose.x);
+	assert_eq!(*x, 0);
+	// lock is kept
+	assert!(databoard.try_get_mut_ref::<Pose>("pose").is_err());
+	drop(x);
+	let entry = databoard.get_ref::<Pose>("pose").unwrap();
+	let entry = EntryReadGuard::try_map(entry, |pose| pose.name.as_ref())
+		.err()
+		.unwrap();
+	drop(entry);
+	assert!(databoard.try_get_mut_ref::<Pose>("pose").is_ok());
+
+	// the write guard is not touched by a read
+	let entry = databoard.get_mut_ref::<Pose>("pose").unwrap();
+	assert_eq!(entry.y, 0);
+	drop(entry);
+	assert_eq!(databoard.sequence_id("pose").unwrap(), 1);
+
+	// modification via the mapped guard
+	let entry = databoard.get_mut_ref::<Pose>("pose").unwrap();
+	let mut y = EntryWriteGuard::map(entry, |pose| &mut pose.y);
+	*y = 4;
+	assert!(databoard.try_get_ref::<Pose>("pose").is_err());
+	drop(y);
+	assert_eq!(databoard.sequence_id("pose").unwrap(), 2);
+
+	// modification within the mapping function is recorded
+	let entry = databoard.get_mut_ref::<Pose>("pose").unwrap();
+	let y = EntryWriteGuard::map(entry, |pose| {
+		pose.x = 1;
+		&mut pose.y
+	});
+	assert_eq!(*y, 4);
+	drop(y);
+	assert_eq!(databoard.sequence_id("pose").unwrap(), 3);
+	let entry = databoard.get_mut_ref::<Pose>("pose").unwrap();
+	let entry = EntryWriteGuard::try_map(entry, |pose| {
+		pose.x = 0;
+		pose.name.as_mut()
+	})
+	.err()
+	.unwrap();
+	drop(entry);
+	assert_eq!(databoard.sequence_id("pose").unwrap(), 4);
+
+	// modification before mapping is kept
+	let mut entry = databoard.get_mut_ref::<Pose>("pose").unwrap();
+	entry.name = Some("pose".into());
+	let name = EntryWriteGuard::try_map(entry, |pose| pose.name.as_mut())
+		.ok()
+		.unwrap();
+	assert_eq!(name.as_str(), "pose");
+	drop(name);
+	assert_eq!(databoard.sequence_id("pose").unwrap(), 5);
+
+	let pose = databoard.get::<Pose>("pose").unwrap();
+	assert_eq!(pose.x, 0);
+	assert_eq!(pose.y, 4);
+	assert_eq!(pose.name.unwrap(), "pose");
+}
+
+#[test]
+fn referencing_across_threads() {
+	let databoard = Databoard::new();
+	assert_eq!(databoard.set("test", 42).unwrap(), None);
+
+	// read guards can be shared and sent to other threads
+	let entry = Arc::new(databoard.get_ref::<i32>("test").unwrap());
+	let handles: Vec<_> = (0..4)
+		.map(|_| {
+			let entry = entry.clone();
+			std::thread::spawn(move || **entry)
+		})
+		.collect();
+	for handle in handles {
+		assert_eq!(handle.join().unwrap(), 42);
+	}
+	drop(entry);
+
+	// write guards can be sent to other threads
+	let entry = databoard.get_mut_ref::<i32>("test").unwrap();
+	std::thread::spawn(move || {
+		let mut entry = entry;
+		*entry += 1;
+	})
+	.join()
+	.unwrap();
+	assert_eq!(databoard.sequence_id("test").unwrap(), 2);
+
+	// upgradable guards can be sent to other threads
+	let entry = databoard
+		.get_upgradable_ref::<i32>("test")
+		.unwrap();
+	std::thread::spawn(move || {
+		let mut entry = entry.upgrade();
+		*entry += 1;
+	})
+	.join()
+	.unwrap();
+	assert_eq!(databoard.get::<i32>("test").unwrap(), 44);
+	assert_eq!(databoard.sequence_id("test").unwrap(), 3);
+}
+
+#[test]
+fn sharding() {
+	for shards in [0, 1, 3, 16] {
+		let databoard = Databoard::with_shards(None, None, false, shards);
+		for i in 0..50 {
+			databoard.set(&format!("key{i}"), i).unwrap();
+		}
+		assert_eq!(databoard.len(), 50);
+		for i in 0..50 {
+			assert_eq!(databoard.get::<i32>(&format!("key{i}")).unwrap(), i);
+		}
+
+		// renaming moves entries between shards
+		for i in 0..50 {
+			databoard
+				.rename(&format!("key{i}"), &format!("renamed{i}"))
+				.unwrap();
+		}
+		assert_eq!(databoard.len(), 50);
+		assert!(!databoard.contains_key("key0"));
+		assert_eq!(databoard.get::<i32>("renamed49").unwrap(), 49);
+
+		for i in 0..50 {
+			assert_eq!(
+				databoard
+					.delete::<i32>(&format!("renamed{i}"))
+					.unwrap(),
+				i
+			);
+		}
+		assert!(databoard.is_empty());
+	}
+}
+
+#[test]
+fn sharding_concurrency() {
+	let databoard = Databoard::new();
+	let handles: Vec<_> = (0..8)
+		.map(|t| {
+			let databoard = databoard.clone();
+			std::thread::spawn(move || {
+				for i in 0..100 {
+					let key = format!("key_{t}_{i}");
+					databoard.set(&key, i).unwrap();
+					assert_eq!(databoard.get::<i32>(&key).unwrap(), i);
+					if i % 2 == 0 {
+						assert_eq!(databoard.delete::<i32>(&key).unwrap(), i);
+					}
+				}
+			})
+		})
+		.collect();
+	for handle in handles {
+		handle.join().unwrap();
+	}
+	assert_eq!(databoard.len(), 8 * 50);
+}
+
+#[test]
+fn local_keys() {
+	let parent = Databoard::new();
+	parent.set("test", 1).unwrap();
+	let mut remappings = Remappings::default();
+	remappings.add("remapped", "{test}").unwrap();
+	let databoard = Databoard::with(Some(parent.clone()), Some(remappings), true);
+
+	// `_` keys are created and updated in the board itself
+	assert!(databoard.set("_test", 2).unwrap().is_none());
+	assert_eq!(databoard.set("_test", 3).unwrap(), Some(2));
+	assert_eq!(databoard.get::<i32>("_test").unwrap(), 3);
+	assert_eq!(parent.get::<i32>("test").unwrap(), 1);
+	assert_eq!(databoard.get::<i32>("test").unwrap(), 1);
+
+	// remappings are not evaluated for local access
+	assert!(
+		databoard
+			.set_local("remapped", 4)
+			.unwrap()
+			.is_none()
+	);
+	assert_eq!(databoard.set_local("_remapped", 5).unwrap(), Some(4));
+	assert_eq!(databoard.get::<i32>("_remapped").unwrap(), 5);
+	assert_eq!(databoard.get::<i32>("remapped").unwrap(), 1);
+
+	databoard.create_local("created", 6).unwrap();
+	assert!(matches!(
+		databoard.create_local("_created", 7),
+		Err(Error::AlreadyExists { .. })
+	));
+	assert_eq!(databoard.get::<i32>("_created").unwrap(), 6);
+	assert!(!parent.contains_key("created"));
+	assert_eq!(databoard.len(), 3);
+	assert_eq!(parent.len(), 1);
+}
+
+#[test]
+fn create_and_update() {
+	let parent = Databoard::new();
+	let mut remappings = Remappings::default();
+	remappings.add("remapped", "{test}").unwrap();
+	remappings.add("constant", "42").unwrap();
+	let databoard = Databoard::with(Some(parent.clone()), Some(remappings), false);
+
+	// create only succeeds for non existing entries
+	databoard.create("remapped", 1).unwrap();
+	assert!(matches!(databoard.create("remapped", 2), Err(Error::AlreadyExists { .. })));
+	assert!(matches!(databoard.create("@test", 3), Err(Error::AlreadyExists { .. })));
+	assert_eq!(parent.get::<i32>("test").unwrap(), 1);
+	databoard.create("_local", 4).unwrap();
+	assert!(matches!(databoard.create("local", 5), Err(Error::AlreadyExists { .. })));
+	assert!(matches!(databoard.create("constant", 6), Err(Error::Assignment { .. })));
+
+	// update only succeeds for existing entries
+	assert_eq!(databoard.update("remapped", 7).unwrap(), 1);
+	assert_eq!(databoard.update("@test", 8).unwrap(), 7);
+	assert_eq!(databoard.update("_local", 9).unwrap(), 4);
+	assert!(matches!(databoard.update("missing", 10), Err(Error::NotFound { .. })));
+	assert!(matches!(databoard.update("local", 11_u8), Err(Error::WrongType { .. })));
+	assert!(!databoard.contains_key("missing"));
+	assert_eq!(parent.get::<i32>("test").unwrap(), 8);
+	assert_eq!(databoard.sequence_id("remapped").unwrap(), 3);
+
+	// lock-free entries are updated lock-free
+	databoard.set_atomic("atomic", 12).unwrap();
+	assert_eq!(databoard.update("atomic", 13).unwrap(), 12);
+	assert!(matches!(databoard.get_ref::<i32>("atomic"), Err(Error::LockFree { .. })));
+}
+
+#[test]
+fn modifications() {
+	let parent = Databoard::new();
+	let databoard = Databoard::with_parent(parent.clone());
+	assert_eq!(parent.modifications(), 0);
+	assert_eq!(databoard.modifications(), 0);
+
+	// changes are counted at the board storing the entry
+	databoard.set("test", 1).unwrap();
+	databoard.set("test", 2).unwrap();
+	assert_eq!(parent.modifications(), 2);
+	assert_eq!(databoard.modifications(), 0);
+	databoard.set("_local", 1).unwrap();
+	assert_eq!(databoard.modifications(), 1);
+
+	// failing operations and reads are not counted
+	assert!(databoard.create("test", 3).is_err());
+	assert!(databoard.update("test", "wrong type").is_err());
+	assert_eq!(databoard.get::<i32>("test").unwrap(), 2);
+	assert_eq!(parent.modifications(), 2);
+
+	// guards count only modifications
+	let guard = databoard.get_mut_ref::<i32>("test").unwrap();
+	drop(guard);
+	assert_eq!(parent.modifications(), 2);
+	*databoard.get_mut_ref::<i32>("test").unwrap() += 1;
+	assert_eq!(parent.modifications(), 3);
+	let guard = databoard
+		.get_upgradable_ref::<i32>("test")
+		.unwrap();
+	*guard.upgrade() += 1;
+	assert_eq!(parent.modifications(), 4);
+	assert_eq!(parent.sequence_id("test").unwrap(), 4);
+
+	// creation, deletion and moving of entries
+	databoard.set_atomic("_atomic", 1_u8).unwrap();
+	databoard.set("_atomic", 2_u8).unwrap();
+	assert_eq!(databoard.modifications(), 3);
+	// moving counts as deletion and creation
+	databoard.rename("_atomic", "_renamed").unwrap();
+	assert_eq!(databoard.modifications(), 5);
+	databoard
+		.move_to("_renamed", &parent, "moved")
+		.unwrap();
+	assert_eq!(databoard.modifications(), 6);
+	assert_eq!(parent.modifications(), 5);
+	assert_eq!(parent.delete::<u8>("moved").unwrap(), 2);
+	assert_eq!(parent.modifications(), 6);
+	// clearing the subtree also deletes the entries of children
+	parent.clear_subtree();
+	assert_eq!(parent.modifications(), 7);
+	assert_eq!(databoard.modifications(), 7);
+	parent.clear_subtree();
+	assert_eq!(parent.modifications(), 7);
+}
+
+#[test]
+fn journal() {
+	let change = |key: &str, operation, sequence_id| Change {
+		key: key.into(),
+		operation,
+		sequence_id,
+	};
+	let parent = Databoard::new();
+	let databoard = Databoard::with_parent(parent.clone());
+
+	// disabled by default, but the cursor is counted up
+	parent.set("test", 1).unwrap();
+	assert_eq!(parent.journal_cursor(), 1);
+	let changes = parent.changes_since(0);
+	assert!(!changes.complete);
+	assert!(changes.changes.is_empty());
+	assert_eq!(changes.cursor, 1);
+
+	parent.set_journal_capacity(16);
+	let cursor = parent.journal_cursor();
+	databoard.set("test", 2).unwrap();
+	*databoard.get_mut_ref::<i32>("test").unwrap() += 1;
+	databoard.create("@created", 1.0).unwrap();
+	databoard.rename("created", "renamed").unwrap();
+	databoard.set_atomic("atomic", true).unwrap();
+	databoard.set("atomic", false).unwrap();
+	assert!(!databoard.delete::<bool>("atomic").unwrap());
+	let changes = parent.changes_since(cursor);
+	assert!(changes.complete);
+	assert_eq!(
+		changes.changes,
+		[
+			change("test", Operation::Update, 2),
+			change("test", Operation::Modify, 3),
+			change("created", Operation::Create, 1),
+			change("created", Operation::Delete, 0),
+			change("renamed", Operation::Create, 1),
+			change("atomic", Operation::Create, 1),
+			change("atomic", Operation::Update, 2),
+			change("atomic", Operation::Delete, 0),
+		]
+	);
+	assert!(databoard.changes_since(0).changes.is_empty());
+
+	// pulling incrementally
+	let cursor = changes.cursor;
+	assert!(parent.changes_since(cursor).changes.is_empty());
+	parent.update("renamed", 2.0).unwrap();
+	parent.clear();
+	let changes = parent.changes_since(cursor);
+	assert!(changes.complete);
+	assert_eq!(changes.changes.len(), 3);
+	assert_eq!(changes.changes[0], change("renamed", Operation::Update, 2));
+	assert_eq!(changes.cursor, parent.journal_cursor());
+
+	// dropped changes
+	parent.set_journal_capacity(1);
+	let changes = parent.changes_since(cursor);
+	assert!(!changes.complete);
+	assert_eq!(changes.changes.len(), 1);
+}
+
+#[test]
+fn read_only_view() {
+	let parent = Databoard::new();
+	parent.set("test", 1).unwrap();
+	parent.set("other", String::from("text")).unwrap();
+	let mut remappings = Remappings::default();
+	remappings.add("remapped", "{test}").unwrap();
+	remappings.add("constant", "42").unwrap();
+	let databoard = Databoard::with(Some(parent.clone()), Some(remappings), true);
+	databoard.set("_local", 2_i64).unwrap();
+
+	// code receiving a view can only read
+	let read = |view: &DataboardView| view.get::<i32>("remapped").unwrap();
+
+	let view = databoard.view();
+	assert_eq!(read(&view), 1);
+	assert!(view.contains_key("remapped"));
+	assert!(view.contains_key("@other"));
+	assert!(!view.contains_key("missing"));
+	assert!(view.contains::<i32>("test").unwrap());
+	assert!(view.contains::<String>("remapped").is_err());
+	assert_eq!(view.get::<i64>("_local").unwrap(), 2);
+	assert_eq!(*view.get_ref::<String>("other").unwrap(), "text");
+	assert_eq!(*view.try_get_ref::<i32>("remapped").unwrap(), 1);
+	assert!(matches!(view.get::<i32>("constant"), Err(Error::Assignment { .. })));
+	assert!(matches!(view.get::<i32>("missing"), Err(Error::NotFound { .. })));
+
+	// changes through the board are visible in the view
+	assert_eq!(view.sequence_id("remapped").unwrap(), 1);
+	parent.set("test", 3).unwrap();
+	assert_eq!(read(&view), 3);
+	assert_eq!(view.sequence_id("@test").unwrap(), 2);
+
+	// a write lock is respected
+	let guard = parent.get_mut_ref::<i32>("test").unwrap();
+	assert!(matches!(view.try_get_ref::<i32>("remapped"), Err(Error::IsLocked { .. })));
+	assert!(matches!(
+		view.get_ref_timeout::<i32>("remapped", Retries::new(2)),
+		Err(Error::IsLocked { .. })
+	));
+	drop(guard);
+
+	let view = DataboardView::from(parent);
+	assert_eq!(view.get::<i32>("test").unwrap(), 3);
+}
+
+#[test]
+fn defaults() {
+	let defaults = Defaults::new();
+	defaults.set("speed", 1_u8).unwrap();
+	defaults.set("top", 10_i32).unwrap();
+	let root = Databoard::with_defaults(None, None, false, defaults);
+	let mut remappings = Remappings::default();
+	remappings.add("remapped", "{top}").unwrap();
+	remappings.add("orphan", "{missing}").unwrap();
+	remappings.add("constant", "42").unwrap();
+	let child = Databoard::with(Some(root.clone()), Some(remappings), false);
+	child.defaults().set("remapped", 20_i32).unwrap();
+	child.defaults().set("local", 30_i32).unwrap();
+	assert_eq!(child.defaults().len(), 2);
+
+	// the defaults of the board responsible for the entry come first
+	assert_eq!(root.get::<u8>("speed").unwrap(), 1);
+	assert_eq!(child.get::<i32>("remapped").unwrap(), 10);
+	root.defaults().delete::<i32>("top").unwrap();
+	assert_eq!(child.get::<i32>("remapped").unwrap(), 20);
+	assert_eq!(child.get::<i32>("local").unwrap(), 30);
+
+	// stored entries take precedence
+	root.set("top", 1_i32).unwrap();
+	assert_eq!(child.get::<i32>("remapped").unwrap(), 1);
+	assert!(!root.contains_key("speed"));
+
+	// errors are still reported
+	assert!(matches!(root.get::<i32>("speed"), Err(Error::WrongType { .. })));
+	assert!(matches!(child.get::<i32>("unknown"), Err(Error::NotFound { .. })));
+
+	// get_or and get_or_default
+	assert_eq!(child.get_or("unknown", 5_i32).unwrap(), 5);
+	assert_eq!(child.get_or("remapped", 5_i32).unwrap(), 1);
+	assert_eq!(child.get_or("local", 5_i32).unwrap(), 30);
+	assert_eq!(child.get_or_default::<i32>("unknown").unwrap(), 0);
+	assert_eq!(child.get_or_default::<String>("unknown").unwrap(), "");
+	assert!(matches!(
+		child.get_or("remapped", String::new()),
+		Err(Error::WrongType { .. })
+	));
+	assert!(matches!(
+		child.get_or_default::<i32>("constant"),
+		Err(Error::Assignment { .. })
+	));
+	let orphan = Databoard::with(None, Some(child.remappings().unwrap().clone()), false);
+	assert!(matches!(orphan.get_or("orphan", 1_i32), Err(Error::NoParent { .. })));
+}
+
+#[test]
+fn deep_clone_and_fork() {
+	let mut registry = CloneRegistry::new();
+	registry.register::<i32>();
+	registry.register::<String>();
+	registry.register::<i32>();
+	assert!(registry.is_registered::<String>());
+
+	let root = Databoard::new();
+	root.set("goal", String::from("dock")).unwrap();
+	let mut remappings = Remappings::default();
+	remappings.add("target", "{goal}").unwrap();
+	let child = Databoard::with(Some(root.clone()), Some(remappings), false);
+	child.set("speed", 1_i32).unwrap();
+	child.set("speed", 2_i32).unwrap();
+	child.set_atomic("counter", 1_u64).unwrap();
+	child.defaults().set("missing", 3_i32).unwrap();
+
+	// a deep clone shares the parent
+	let clone = child.deep_clone(&registry).unwrap();
+	assert_eq!(clone.len(), 2);
+	assert_eq!(clone.get::<i32>("speed").unwrap(), 2);
+	assert_eq!(clone.sequence_id("speed").unwrap(), 2);
+	assert_eq!(clone.get::<u64>("counter").unwrap(), 1);
+	assert_eq!(clone.get::<i32>("missing").unwrap(), 3);
+	assert_eq!(clone.get::<String>("target").unwrap(), "dock");
+	assert_eq!(clone.level(), 1);
+	assert_eq!(root.children().len(), 2);
+
+	clone.set("speed", 4_i32).unwrap();
+	clone.set_atomic("counter", 5_u64).unwrap();
+	assert_eq!(child.get::<i32>("speed").unwrap(), 2);
+	assert_eq!(child.get::<u64>("counter").unwrap(), 1);
+	assert_eq!(child.sequence_id("counter").unwrap(), 1);
+	root.set("goal", String::from("charger")).unwrap();
+	assert_eq!(clone.get::<String>("target").unwrap(), "charger");
+
+	// a fork is independent of the whole original hierarchy
+	let fork = child.fork(&registry).unwrap();
+	assert_eq!(fork.get::<String>("target").unwrap(), "charger");
+	assert_eq!(fork.sequence_id("target").unwrap(), 2);
+	fork.set("target", String::from("home")).unwrap();
+	assert_eq!(root.get::<String>("goal").unwrap(), "charger");
+	assert_eq!(fork.get::<String>("@goal").unwrap(), "home");
+	assert_eq!(fork.level(), 1);
+
+	// unregistered types are reported
+	child.set("unregistered", 1.5_f64).unwrap();
+	assert!(matches!(child.deep_clone(&registry), Err(Error::Unregistered { key }) if key.as_ref() == "unregistered"));
+	assert!(matches!(
+		child.fork(&CloneRegistry::new()),
+		Err(Error::Unregistered { key }) if key.as_ref() == "goal"
+	));
+}
+
+#[test]
+fn diff_and_merge() {
+	let mut registry = CloneRegistry::new();
+	registry.register::<i32>();
+	registry.register_comparable::<String>();
+	assert!(registry.is_comparable::<String>());
+	assert!(!registry.is_comparable::<i32>());
+
+	let expected = Databoard::new();
+	expected
+		.set("goal", String::from("dock"))
+		.unwrap();
+	expected.set("speed", 1_i32).unwrap();
+	expected.set("only_expected", 1_i32).unwrap();
+	let actual = Databoard::new();
+	actual.set("goal", String::from("dock")).unwrap();
+	actual.set("speed", 1_i32).unwrap();
+	actual.set("only_actual", 2_i32).unwrap();
+	assert!(expected.diff(&expected, &registry).is_empty());
+
+	// comparable types are compared by value, others by sequence id
+	actual
+		.set("goal", String::from("charger"))
+		.unwrap();
+	actual.set("goal", String::from("dock")).unwrap();
+	let diff = expected.diff(&actual, &registry);
+	assert_eq!(diff.added, ["only_actual".into()]);
+	assert_eq!(diff.removed, ["only_expected".into()]);
+	assert!(diff.changed.is_empty());
+	actual.set("speed", 1_i32).unwrap();
+	actual.set("speed", 1_i32).unwrap();
+	actual
+		.set("goal", String::from("charger"))
+		.unwrap();
+	let diff = expected.diff(&actual, &registry);
+	assert_eq!(diff.changed, ["goal".into(), "speed".into()]);
+
+	// an error policy merges nothing
+	assert!(matches!(
+		expected.merge_from(&actual, &registry, MergePolicy::Error),
+		Err(Error::Conflict { key }) if key.as_ref() == "goal"
+	));
+	assert!(!expected.contains_key("only_actual"));
+
+	// a keep policy only adds entries
+	let merged = expected
+		.merge_from(&actual, &registry, MergePolicy::Keep)
+		.unwrap();
+	assert_eq!(merged, diff);
+	assert_eq!(expected.get::<i32>("only_actual").unwrap(), 2);
+	assert_eq!(expected.get::<String>("goal").unwrap(), "dock");
+
+	// an overwrite policy replaces the changed entries taking over their sequence id
+	expected
+		.merge_from(&actual, &registry, MergePolicy::Overwrite)
+		.unwrap();
+	assert_eq!(expected.get::<String>("goal").unwrap(), "charger");
+	assert_eq!(expected.sequence_id("goal").unwrap(), actual.sequence_id("goal").unwrap());
+	assert_eq!(expected.get::<i32>("only_expected").unwrap(), 1);
+	// so values of not comparable types do not differ anymore
+	let diff = expected.diff(&actual, &registry);
+	assert_eq!(diff.removed, ["only_expected".into()]);
+	assert!(diff.added.is_empty());
+	assert!(diff.changed.is_empty());
+
+	// referenced entries can not be replaced
+	actual.set("speed", 2_i32).unwrap();
+	let guard = expected.get_ref::<i32>("speed").unwrap();
+	assert!(matches!(
+		expected.merge_from(&actual, &registry, MergePolicy::Overwrite),
+		Err(Error::InUse { key }) if key.as_ref() == "speed"
+	));
+	drop(guard);
+}
+
+#[test]
+fn error_context() {
+	let root = Databoard::new();
+	root.set("speed", 1_i32).unwrap();
+	let mut remappings = Remappings::default();
+	remappings.add("velocity", "{speed}").unwrap();
+	remappings.add("target", "{goal}").unwrap();
+	let child = Databoard::with(Some(root), Some(remappings), false);
+
+	// a missing entry reports where the lookup failed
+	let error = child.get::<String>("target").unwrap_err();
+	assert!(matches!(&error, Error::NotFound { key, .. } if key.as_ref() == "goal"));
+	let context = error.context().unwrap();
+	assert_eq!(context.original.as_deref(), Some("target"));
+	assert_eq!(context.remapped.as_deref(), Some("goal"));
+	assert_eq!(context.level, Some(0));
+	assert_eq!(
+		error.to_string(),
+		"an entry for the key goal is not existing, accessed as target, remapped to goal at level 0"
+	);
+	let error = child.sequence_id("unknown").unwrap_err();
+	assert_eq!(error.context().unwrap().level, Some(1));
+
+	// a wrong type reports the expected type
+	let error = child.get::<String>("velocity").unwrap_err();
+	let context = error.context().unwrap();
+	assert_eq!(context.original.as_deref(), Some("velocity"));
+	assert_eq!(context.remapped.as_deref(), Some("speed"));
+	assert_eq!(context.expected, Some("alloc::string::String"));
+	assert!(matches!(
+		child.update("@speed", 2_u8),
+		Err(Error::WrongType { context: Some(_), .. })
+	));
+
+	// other errors have no context
+	assert!(
+		child
+			.create("velocity", 3_i32)
+			.unwrap_err()
+			.context()
+			.is_none()
+	);
+}
+
+#[test]
+fn type_names() {
+	let root = Databoard::new();
+	root.set("goal", String::from("dock")).unwrap();
+	root.set_atomic("counter", 1_u64).unwrap();
+	let mut remappings = Remappings::default();
+	remappings.add("target", "{goal}").unwrap();
+	let child = Databoard::with(Some(root), Some(remappings), false);
+	assert_eq!(child.type_name_of("target").unwrap(), "alloc::string::String");
+	assert_eq!(child.type_name_of("@counter").unwrap(), "u64");
+	assert_eq!(child.view().type_name_of("@goal").unwrap(), "alloc::string::String");
+	assert!(matches!(child.type_name_of("missing"), Err(Error::NotFound { .. })));
+
+	// a wrong type reports the expected and the stored type
+	let error = child.get::<i32>("target").unwrap_err();
+	let context = error.context().unwrap();
+	assert_eq!(context.expected, Some("i32"));
+	assert_eq!(context.stored, Some("alloc::string::String"));
+	assert_eq!(
+		error.to_string(),
+		"the entry for the key goal is stored with a different type, accessed as target, remapped to goal at level 0, \
+		 expected type i32, stored type alloc::string::String"
+	);
+	let error = child.update("@counter", 2_i32).unwrap_err();
+	assert_eq!(error.context().unwrap().stored, Some("u64"));
+	let error = child.get_ref::<u8>("target").err().unwrap();
+	assert_eq!(error.context().unwrap().stored, Some("alloc::string::String"));
+}
@@ -0,0 +1,148 @@
+// Copyright © 2025 Stephan Kunz
+//! Integration tests for [`Overlay`].
+
+#![allow(clippy::cognitive_complexity)]
+#![allow(unused)]
+#![allow(missing_docs)]
+#![allow(clippy::unwrap_used)]
+
+use databoard::{Databoard, Error, Overlay, Remappings};
+
+#[test]
+fn commit() {
+	let parent = Databoard::new();
+	parent.set("speed", 1_i32).unwrap();
+	parent.set("path", vec![1, 2]).unwrap();
+	parent
+		.set("obsolete", String::from("old"))
+		.unwrap();
+	let mut remappings = Remappings::default();
+	remappings.add("target", "{goal}").unwrap();
+	let overlay = Overlay::with(&parent, Some(remappings));
+	assert!(overlay.is_empty());
+
+	// reads fall through to the parent
+	assert_eq!(overlay.get::<i32>("speed").unwrap(), 1);
+	assert_eq!(*overlay.get_ref::<Vec<i32>>("path").unwrap(), [1, 2]);
+	assert!(overlay.contains_key("obsolete"));
+
+	// writes stay in the overlay
+	overlay.set("speed", 2_i32).unwrap();
+	overlay
+		.set("target", String::from("dock"))
+		.unwrap();
+	overlay
+		.get_mut_ref::<Vec<i32>>("path")
+		.unwrap()
+		.push(3);
+	overlay.delete("obsolete").unwrap();
+	assert_eq!(overlay.len(), 4);
+
+	assert_eq!(overlay.get::<i32>("speed").unwrap(), 2);
+	assert_eq!(overlay.get::<String>("target").unwrap(), "dock");
+	assert_eq!(*overlay.get_ref::<Vec<i32>>("path").unwrap(), [1, 2, 3]);
+	assert!(!overlay.contains_key("obsolete"));
+	assert!(matches!(overlay.get::<String>("obsolete"), Err(Error::NotFound { .. })));
+	assert!(matches!(overlay.delete("obsolete"), Err(Error::NotFound { .. })));
+
+	assert_eq!(parent.get::<i32>("speed").unwrap(), 1);
+	assert!(!parent.contains_key("goal"));
+	assert_eq!(parent.get::<Vec<i32>>("path").unwrap(), [1, 2]);
+	assert!(parent.contains_key("obsolete"));
+
+	// type errors are detected before committing
+	assert!(matches!(overlay.set("speed", 1.0_f64), Err(Error::WrongType { .. })));
+	assert!(matches!(overlay.get::<f64>("speed"), Err(Error::WrongType { .. })));
+
+	// commit pushes the changes respecting the remappings
+	overlay.commit().unwrap();
+	assert!(overlay.is_empty());
+	assert_eq!(parent.get::<i32>("speed").unwrap(), 2);
+	assert_eq!(parent.sequence_id("speed").unwrap(), 2);
+	assert_eq!(parent.get::<String>("goal").unwrap(), "dock");
+	assert_eq!(parent.get::<Vec<i32>>("path").unwrap(), [1, 2, 3]);
+	assert!(!parent.contains_key("obsolete"));
+	assert_eq!(overlay.get::<i32>("speed").unwrap(), 2);
+}
+
+#[test]
+fn discard() {
+	let parent = Databoard::new();
+	parent.set("speed", 1_i32).unwrap();
+	let overlay = Overlay::new(&parent);
+	overlay.set("speed", 2_i32).unwrap();
+	overlay.set("new", 3_i32).unwrap();
+	*overlay.get_mut_ref::<i32>("new").unwrap() += 1;
+	assert_eq!(overlay.get::<i32>("new").unwrap(), 4);
+	assert_eq!(overlay.sequence_id("new").unwrap(), 2);
+
+	overlay.discard();
+	assert!(overlay.is_empty());
+	assert_eq!(overlay.get::<i32>("speed").unwrap(), 1);
+	assert!(!overlay.contains_key("new"));
+	overlay.commit().unwrap();
+	assert_eq!(parent.get::<i32>("speed").unwrap(), 1);
+	assert!(!parent.contains_key("new"));
+
+	// dropping discards as well
+	let overlay = Overlay::new(&parent);
+	overlay.set("speed", 2_i32).unwrap();
+	drop(overlay);
+	assert_eq!(parent.get::<i32>("speed").unwrap(), 1);
+}
+
+#[test]
+fn failing_commit() {
+	let parent = Databoard::new();
+	parent.set("a", 1_i32).unwrap();
+	let mut remappings = Remappings::default();
+	remappings.add("constant", "42").unwrap();
+	let overlay = Overlay::with(&parent, Some(remappings));
+	assert!(matches!(overlay.set("constant", 1_i32), Err(Error::Assignment { .. })));
+
+	overlay.set("a", 2_i32).unwrap();
+	overlay.set("b", 3_i32).unwrap();
+	// the parent entry changes its type meanwhile
+	parent.delete::<i32>("a").unwrap();
+	parent.set("a", String::from("a")).unwrap();
+	assert!(matches!(overlay.commit(), Err(Error::WrongType { .. })));
+	assert_eq!(overlay.len(), 2);
+	assert!(!parent.contains_key("b"));
+
+	parent.delete::<String>("a").unwrap();
+	overlay.commit().unwrap();
+	assert_eq!(parent.get::<i32>("a").unwrap(), 2);
+	assert_eq!(parent.get::<i32>("b").unwrap(), 3);
+}
+
+#[test]
+fn resolved_targets() {
+	let parent = Databoard::new();
+	parent.set("x", 1_i32).unwrap();
+	let mut remappings = Remappings::default();
+	remappings.add("alias", "{x}").unwrap();
+	let overlay = Overlay::with(&parent, Some(remappings));
+	// the overlay is not a child of the parent
+	assert!(parent.children().is_empty());
+
+	// all keys resolving to the same entry share one pending change
+	overlay.set("alias", 2_i32).unwrap();
+	assert_eq!(overlay.len(), 1);
+	assert_eq!(overlay.get::<i32>("x").unwrap(), 2);
+	assert_eq!(overlay.get::<i32>("@x").unwrap(), 2);
+	assert_eq!(overlay.get::<i32>("_x").unwrap(), 2);
+	*overlay.get_mut_ref::<i32>("@x").unwrap() += 1;
+	assert_eq!(overlay.len(), 1);
+	assert_eq!(overlay.get::<i32>("alias").unwrap(), 3);
+	overlay.delete("_x").unwrap();
+	assert!(!overlay.contains_key("alias"));
+	assert!(!overlay.contains_key("x"));
+	assert_eq!(overlay.len(), 1);
+
+	// local keys are resolved against the parent
+	overlay.set("_y", 5_i32).unwrap();
+	assert_eq!(overlay.get::<i32>("y").unwrap(), 5);
+	overlay.commit().unwrap();
+	assert!(!parent.contains_key("x"));
+	assert_eq!(parent.get::<i32>("y").unwrap(), 5);
+}
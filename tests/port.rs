@@ -0,0 +1,183 @@
+// Copyright © 2025 Stephan Kunz
+//! Integration tests for [`InputPort`] and [`OutputPort`].
+
+#![allow(clippy::cognitive_complexity)]
+#![allow(unused)]
+#![allow(missing_docs)]
+#![allow(clippy::unwrap_used)]
+
+use core::str::FromStr;
+use databoard::{Databoard, Defaults, Error, InputPort, OutputPort, Remappings};
+
+#[derive(Clone, Debug, PartialEq)]
+struct Point {
+	x: i32,
+	y: i32,
+}
+
+impl FromStr for Point {
+	type Err = ();
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		let (x, y) = s.split_once(';').ok_or(())?;
+		Ok(Self {
+			x: x.parse().map_err(|_| ())?,
+			y: y.parse().map_err(|_| ())?,
+		})
+	}
+}
+
+fn boards() -> (Databoard, Databoard, Databoard) {
+	let root = Databoard::new();
+	let mut remappings = Remappings::default();
+	remappings.add("target", "{goal}").unwrap();
+	remappings.add("same", "{=}").unwrap();
+	let middle = Databoard::with(Some(root.clone()), Some(remappings), false);
+	let mut remappings = Remappings::default();
+	remappings.add("input", "{target}").unwrap();
+	remappings.add("same", "{=}").unwrap();
+	remappings.add("top", "{@top}").unwrap();
+	remappings.add("local", "{_local}").unwrap();
+	remappings.add("point", "1;2").unwrap();
+	remappings.add("speed", "42").unwrap();
+	remappings.add("invalid", "1,2").unwrap();
+	let leaf = Databoard::with(Some(middle.clone()), Some(remappings), false);
+	(root, middle, leaf)
+}
+
+#[test]
+fn input_and_output() {
+	let (root, middle, leaf) = boards();
+
+	let output = OutputPort::<i32>::new("input", &leaf);
+	assert_eq!(output.name(), "input");
+	assert_eq!(output.set(1).unwrap(), None);
+	assert_eq!(root.get::<i32>("goal").unwrap(), 1);
+	assert_eq!(output.set(2).unwrap(), Some(1));
+	let input = InputPort::<i32>::new("input", &leaf);
+	assert_eq!(input.get().unwrap(), 2);
+
+	OutputPort::new("same", &leaf)
+		.set(String::from("same"))
+		.unwrap();
+	assert_eq!(root.get::<String>("same").unwrap(), "same");
+	assert_eq!(
+		InputPort::<String>::new("same", &leaf)
+			.get()
+			.unwrap(),
+		"same"
+	);
+
+	OutputPort::new("top", &leaf).set(3_i32).unwrap();
+	assert_eq!(root.get::<i32>("top").unwrap(), 3);
+	OutputPort::new("local", &leaf)
+		.set(4_i32)
+		.unwrap();
+	assert_eq!(middle.get::<i32>("_local").unwrap(), 4);
+	assert_eq!(
+		InputPort::<i32>::new("local", &leaf)
+			.get()
+			.unwrap(),
+		4
+	);
+	OutputPort::new("unmapped", &leaf)
+		.set(5_i32)
+		.unwrap();
+	assert_eq!(leaf.get::<i32>("unmapped").unwrap(), 5);
+
+	// constant assignments
+	assert_eq!(
+		InputPort::<i32>::new("speed", &leaf)
+			.get()
+			.unwrap(),
+		42
+	);
+	assert_eq!(
+		InputPort::<Point>::new("point", &leaf)
+			.get()
+			.unwrap(),
+		Point { x: 1, y: 2 }
+	);
+}
+
+#[test]
+fn errors() {
+	let (root, middle, leaf) = boards();
+	root.set("goal", 1_i32).unwrap();
+
+	let error = InputPort::<String>::new("input", &leaf)
+		.get()
+		.unwrap_err();
+	assert!(matches!(
+		&error,
+		Error::Port { port, remapped, level: 0, source }
+			if port.as_ref() == "input" && remapped.as_ref() == "goal" && matches!(**source, Error::WrongType { .. })
+	));
+	assert_eq!(
+		error.to_string(),
+		"port input remapped to goal at level 0: the entry for the key goal is stored with a different type, accessed as input, \
+		 remapped to goal at level 0, expected type alloc::string::String, stored type i32"
+	);
+	assert!(core::error::Error::source(&error).is_some());
+	if let Error::Port { source, .. } = &error {
+		let context = source.context().unwrap();
+		assert_eq!(context.original.as_deref(), Some("input"));
+		assert_eq!(context.level, Some(0));
+	}
+
+	let error = InputPort::<i32>::new("missing", &leaf)
+		.get()
+		.unwrap_err();
+	assert!(matches!(
+		&error,
+		Error::Port { port, remapped, level: 2, source }
+			if port.as_ref() == "missing" && remapped.as_ref() == "missing" && matches!(**source, Error::NotFound { .. })
+	));
+
+	let error = InputPort::<Point>::new("invalid", &leaf)
+		.get()
+		.unwrap_err();
+	assert!(matches!(
+		&error,
+		Error::Port { port, remapped, level: 2, source }
+			if port.as_ref() == "invalid" && remapped.as_ref() == "1,2" && matches!(**source, Error::Parse { .. })
+	));
+	assert_eq!(
+		error.to_string(),
+		"port invalid remapped to 1,2 at level 2: assignment 1,2 of invalid can not be parsed"
+	);
+
+	let error = OutputPort::new("speed", &leaf)
+		.set(1_i32)
+		.unwrap_err();
+	assert!(matches!(
+		&error,
+		Error::Port { port, remapped, level: 2, source }
+			if port.as_ref() == "speed" && remapped.as_ref() == "42" && matches!(**source, Error::Assignment { .. })
+	));
+
+	let mut remappings = Remappings::default();
+	remappings.add("orphan", "{parent}").unwrap();
+	let orphan = Databoard::with(None, Some(remappings), false);
+	let error = InputPort::<i32>::new("orphan", &orphan)
+		.get()
+		.unwrap_err();
+	assert!(matches!(
+		&error,
+		Error::Port { port, remapped, level: 0, source }
+			if port.as_ref() == "orphan" && remapped.as_ref() == "parent" && matches!(**source, Error::NoParent { .. })
+	));
+}
+
+#[test]
+fn defaults() {
+	let root = Databoard::new();
+	let defaults = Defaults::new();
+	defaults.set("speed", 1_i32).unwrap();
+	let board = Databoard::with_defaults(Some(root.clone()), None, true, defaults);
+
+	let input = InputPort::<i32>::new("speed", &board);
+	assert_eq!(input.get().unwrap(), 1);
+	root.set("speed", 2_i32).unwrap();
+	assert_eq!(input.get().unwrap(), 2);
+}
@@ -0,0 +1,110 @@
+// Copyright © 2025 Stephan Kunz
+//! Integration tests for remote access to a [`Databoard`].
+
+#![cfg(all(feature = "std", unix))]
+#![allow(clippy::cognitive_complexity)]
+#![allow(unused)]
+#![allow(missing_docs)]
+#![allow(clippy::unwrap_used)]
+
+use databoard::{Databoard, DataboardServer, Error, Registry, RemoteDataboard};
+use std::{io::Write, os::unix::net::UnixStream, sync::Arc};
+
+fn registry() -> Arc<Registry> {
+	let mut registry = Registry::new();
+	registry.register::<i32>("i32").unwrap();
+	registry.register::<String>("String").unwrap();
+	registry.register::<Vec<f64>>("Vec<f64>").unwrap();
+	assert!(matches!(registry.register::<i32>("int"), Err(Error::AlreadyExists { .. })));
+	assert!(matches!(registry.register::<u32>("i32"), Err(Error::AlreadyExists { .. })));
+	Arc::new(registry)
+}
+
+#[test]
+fn unix_socket() {
+	let path = std::env::temp_dir().join(format!("databoard-{}.sock", std::process::id()));
+	let _ = std::fs::remove_file(&path);
+
+	let board = Databoard::new();
+	board.set("local", 1_i32).unwrap();
+	board.set("unregistered", 1_u8).unwrap();
+	let server = DataboardServer::bind(&path, board.clone(), registry()).unwrap();
+	let server = server.spawn();
+
+	let mut first = RemoteDataboard::connect(&path, registry()).unwrap();
+	let mut second = RemoteDataboard::connect(&path, registry()).unwrap();
+
+	// changes of the serving process are visible
+	assert!(first.contains_key("local").unwrap());
+	assert_eq!(first.get::<i32>("local").unwrap(), 1);
+	assert_eq!(first.sequence_id("local").unwrap(), 1);
+	board.set("local", 2_i32).unwrap();
+	assert_eq!(first.get::<i32>("local").unwrap(), 2);
+	assert_eq!(first.sequence_id("local").unwrap(), 2);
+
+	// changes of one client are visible to the serving process and to other clients
+	assert_eq!(
+		first
+			.set("remote", &String::from("hello"))
+			.unwrap(),
+		None
+	);
+	assert_eq!(board.get::<String>("remote").unwrap(), "hello");
+	assert_eq!(second.get::<String>("remote").unwrap(), "hello");
+	assert_eq!(
+		second
+			.set("remote", &String::from("world"))
+			.unwrap(),
+		Some(String::from("hello"))
+	);
+	assert_eq!(first.get::<String>("remote").unwrap(), "world");
+	assert_eq!(first.sequence_id("remote").unwrap(), 2);
+	second.set("vec", &vec![1.0_f64, 2.5]).unwrap();
+	assert_eq!(board.get::<Vec<f64>>("vec").unwrap(), vec![1.0, 2.5]);
+
+	// errors are transferred
+	assert!(!first.contains_key("missing").unwrap());
+	assert!(matches!(first.get::<i32>("missing"), Err(Error::NotFound { .. })));
+	assert!(matches!(first.sequence_id("missing"), Err(Error::NotFound { .. })));
+	assert!(matches!(first.get::<i32>("remote"), Err(Error::WrongType { .. })));
+	assert!(matches!(first.set("remote", &1_i32), Err(Error::WrongType { .. })));
+	assert!(matches!(first.get::<i32>("unregistered"), Err(Error::Unregistered { .. })));
+	assert!(matches!(first.set("u16", &1_u16), Err(Error::Unregistered { .. })));
+
+	// deletion
+	assert!(matches!(first.delete::<i32>("remote"), Err(Error::WrongType { .. })));
+	assert!(board.contains_key("remote"));
+	assert_eq!(first.delete::<String>("remote").unwrap(), "world");
+	assert!(!board.contains_key("remote"));
+	assert!(!second.contains_key("remote").unwrap());
+
+	// shutdown closes the remaining connections
+	drop(first);
+	server.shutdown().unwrap();
+	assert!(matches!(second.contains_key("local"), Err(Error::Remote { .. })));
+	assert!(RemoteDataboard::connect(&path, registry()).is_err());
+	assert!(!path.exists());
+}
+
+#[test]
+fn restart() {
+	let path = std::env::temp_dir().join(format!("databoard-restart-{}.sock", std::process::id()));
+	let _ = std::fs::remove_file(&path);
+	let board = Databoard::new();
+
+	let server = DataboardServer::bind(&path, board.clone(), registry())
+		.unwrap()
+		.spawn();
+	// a client breaking off in the middle of a frame does not fail the server
+	let mut broken = UnixStream::connect(&path).unwrap();
+	broken.write_all(&[8, 0, 0, 0, 1]).unwrap();
+	drop(broken);
+	server.shutdown().unwrap();
+	assert!(!path.exists());
+
+	// the same path can be used again
+	let server = DataboardServer::bind(&path, board, registry()).unwrap();
+	assert!(path.exists());
+	drop(server);
+	assert!(!path.exists());
+}
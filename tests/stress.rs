@@ -0,0 +1,199 @@
+// Copyright © 2025 Stephan Kunz
+//! Multithreaded stress tests for a hierarchy of [`Databoard`]s.
+
+#![allow(unused)]
+#![allow(missing_docs)]
+#![allow(clippy::unwrap_used)]
+#![allow(clippy::cognitive_complexity)]
+
+use databoard::{Databoard, Error, Operation, Remappings, SequenceId};
+use std::{
+	collections::BTreeMap,
+	sync::{Arc, Barrier},
+	thread,
+};
+
+const THREADS: usize = 8;
+const ROUNDS: usize = 200;
+
+/// Creates a root board with a manually remapped child and an auto remapped grandchild.
+/// The key `value` of the child is remapped to `shared` of the root.
+fn hierarchy() -> (Databoard, Databoard, Databoard) {
+	let root = Databoard::new();
+	let mut remappings = Remappings::default();
+	remappings.add("value", "{shared}").unwrap();
+	let child = Databoard::with(Some(root.clone()), Some(remappings), false);
+	let grandchild = Databoard::with_parent(child.clone());
+	(root, child, grandchild)
+}
+
+#[test]
+fn concurrent_creation() {
+	for round in 0..ROUNDS {
+		let (root, child, grandchild) = hierarchy();
+		let barrier = Arc::new(Barrier::new(THREADS));
+		// all threads have to be spawned before joining them
+		#[allow(clippy::needless_collect)]
+		let handles: Vec<_> = (0..THREADS)
+			.map(|t| {
+				let board = [&root, &child, &grandchild][t % 3].clone();
+				let key = if t % 3 == 0 { "shared" } else { "value" };
+				let barrier = barrier.clone();
+				thread::spawn(move || {
+					barrier.wait();
+					board.set(key, t).unwrap()
+				})
+			})
+			.collect();
+		let created = handles
+			.into_iter()
+			.map(|handle| handle.join().unwrap())
+			.filter(Option::is_none)
+			.count();
+		// exactly one thread created the entry, all others updated it
+		assert_eq!(created, 1);
+		assert_eq!(root.len(), 1);
+		assert!(child.is_empty());
+		assert!(grandchild.is_empty());
+		assert_eq!(root.sequence_id("shared").unwrap(), THREADS as SequenceId);
+	}
+}
+
+#[test]
+fn concurrent_atomic_creation() {
+	for round in 0..ROUNDS {
+		let (root, child, grandchild) = hierarchy();
+		let barrier = Arc::new(Barrier::new(THREADS));
+		// all threads have to be spawned before joining them
+		#[allow(clippy::needless_collect)]
+		let handles: Vec<_> = (0..THREADS)
+			.map(|t| {
+				let board = [&root, &child, &grandchild][t % 3].clone();
+				let key = if t % 3 == 0 { "shared" } else { "value" };
+				let barrier = barrier.clone();
+				thread::spawn(move || {
+					barrier.wait();
+					board.set_atomic(key, t).unwrap()
+				})
+			})
+			.collect();
+		let created = handles
+			.into_iter()
+			.map(|handle| handle.join().unwrap())
+			.filter(Option::is_none)
+			.count();
+		assert_eq!(created, 1);
+		assert_eq!(root.len(), 1);
+		assert_eq!(root.sequence_id("shared").unwrap(), THREADS as SequenceId);
+	}
+}
+
+#[test]
+fn concurrent_set_get_delete() {
+	let (root, child, grandchild) = hierarchy();
+	let barrier = Arc::new(Barrier::new(THREADS));
+	let handles: Vec<_> = (0..THREADS)
+		.map(|t| {
+			let board = [&root, &child, &grandchild][t % 3].clone();
+			let barrier = barrier.clone();
+			thread::spawn(move || {
+				barrier.wait();
+				for i in 0..ROUNDS {
+					// entries shared by all threads
+					let key = if t % 3 == 0 { "shared" } else { "value" };
+					board.set(key, i).unwrap();
+					let result = board.get::<usize>(key);
+					assert!(matches!(result, Ok(value) if value < ROUNDS) || matches!(result, Err(Error::NotFound { .. })));
+					if i % 3 == 0 {
+						// concurrent `get`s and `set`s do not hold the entry
+						let result = board.delete::<usize>(key);
+						assert!(
+							matches!(result, Ok(value) if value < ROUNDS) || matches!(result, Err(Error::NotFound { .. }))
+						);
+					}
+
+					// entries private to a thread
+					let key = format!("private_{t}");
+					assert_eq!(board.set(&key, i).unwrap(), i.checked_sub(1));
+					assert_eq!(board.get::<usize>(&key).unwrap(), i);
+				}
+			})
+		})
+		.collect();
+	for handle in handles {
+		handle.join().unwrap();
+	}
+
+	// private entries of the child and grandchild threads are stored in the child
+	for t in 0..THREADS {
+		let key = format!("private_{t}");
+		let board = if t % 3 == 0 { &root } else { &child };
+		assert_eq!(board.get::<usize>(&key).unwrap(), ROUNDS - 1);
+	}
+	assert!(grandchild.is_empty());
+	assert_eq!(child.len(), THREADS - THREADS.div_ceil(3));
+}
+
+#[test]
+fn concurrent_journal() {
+	const KEYS: [&str; 2] = ["a", "b"];
+	const CHANGES: usize = 20 * ROUNDS;
+	let board = Databoard::new();
+	board.set_journal_capacity(THREADS * CHANGES * 2);
+	let barrier = Arc::new(Barrier::new(THREADS));
+	// all threads have to be spawned before joining them
+	#[allow(clippy::needless_collect)]
+	let handles: Vec<_> = (0..THREADS)
+		.map(|t| {
+			let board = board.clone();
+			let barrier = barrier.clone();
+			thread::spawn(move || {
+				barrier.wait();
+				for i in 0..CHANGES {
+					let key = KEYS[(t + i) % KEYS.len()];
+					// the results depend on the other threads, guards are left out
+					// as their modifications are recorded under the key they were taken for
+					match (t + i) % 5 {
+						0 => drop(board.create(key, i)),
+						1 => drop(board.set(key, i)),
+						2 => drop(board.update(key, i)),
+						3 => drop(board.delete::<usize>(key)),
+						_ => drop(board.rename(key, KEYS[(t + i + 1) % KEYS.len()])),
+					}
+				}
+			})
+		})
+		.collect();
+	for handle in handles {
+		handle.join().unwrap();
+	}
+
+	// replaying the journal has to result in the final state of the board
+	let changes = board.changes_since(0);
+	assert!(changes.complete);
+	let mut replayed = BTreeMap::<Arc<str>, SequenceId>::new();
+	for change in changes.changes {
+		match change.operation {
+			Operation::Create => assert!(
+				replayed
+					.insert(change.key, change.sequence_id)
+					.is_none()
+			),
+			Operation::Update | Operation::Modify => assert!(
+				replayed
+					.insert(change.key, change.sequence_id)
+					.is_some()
+			),
+			Operation::Delete => assert!(replayed.remove(&change.key).is_some()),
+		}
+	}
+	let state: BTreeMap<Arc<str>, SequenceId> = board
+		.keys()
+		.into_iter()
+		.map(|key| {
+			let sequence_id = board.sequence_id(&key).unwrap();
+			(key, sequence_id)
+		})
+		.collect();
+	assert_eq!(replayed, state);
+}